@@ -0,0 +1,30 @@
+/// The bytes of `s` if it is all ASCII, so each byte is one character. The
+/// stable counterpart of the nightly `str::as_ascii`.
+pub fn as_ascii(s: &str) -> Option<&[u8]> {
+    s.is_ascii().then_some(s.as_bytes())
+}
+
+/// Splits ASCII text on `separator`, ignoring surrounding whitespace such as
+/// the trailing newline of puzzle inputs. Returns `None` if `s` is not ASCII.
+pub fn split_ascii(s: &str, separator: u8) -> Option<impl Iterator<Item = &[u8]>> {
+    let bytes = as_ascii(s)?.trim_ascii();
+    Some(bytes.split(move |byte| *byte == separator))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_as_ascii() {
+        assert_eq!(Some(b"rn=1".as_slice()), as_ascii("rn=1"));
+        assert_eq!(None, as_ascii("rn=½"));
+    }
+
+    #[test]
+    fn test_split_ascii() {
+        let words: Vec<&[u8]> = split_ascii("rn=1,cm-\n", b',').unwrap().collect();
+        assert_eq!(vec![b"rn=1".as_slice(), b"cm-".as_slice()], words);
+        assert!(split_ascii("ü,a", b',').is_none());
+    }
+}
@@ -0,0 +1,5 @@
+//! Small helpers shared between days, standing in for nightly-only ASCII
+//! APIs so that the whole workspace builds on stable, plus a seeded generator
+//! for benchmark inputs.
+pub mod ascii;
+pub mod rng;
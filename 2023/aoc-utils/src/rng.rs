@@ -0,0 +1,45 @@
+/// Seeded linear congruential generator for benchmark inputs, so runs are
+/// comparable without pulling in `rand`.
+#[derive(Debug, Clone)]
+pub struct Lcg {
+    state: u64,
+}
+
+impl Default for Lcg {
+    fn default() -> Lcg {
+        Lcg::new(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+impl Lcg {
+    pub fn new(seed: u64) -> Lcg {
+        Lcg { state: seed }
+    }
+
+    /// A value in `0..bound`, taken from the high bits of the state.
+    pub fn below(&mut self, bound: usize) -> usize {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.state >> 33) % bound as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_below() {
+        let values: Vec<usize> = std::iter::repeat_with({
+            let mut rng = Lcg::default();
+            move || rng.below(10)
+        })
+        .take(100)
+        .collect();
+        assert!(values.iter().all(|value| *value < 10));
+        let mut rng = Lcg::default();
+        assert_eq!(values[..5], [(); 5].map(|_| rng.below(10)));
+    }
+}
@@ -4,7 +4,7 @@ use criterion::{
 use day_10::*;
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
-    let input = include_str!("../input1.txt");
+    let input = include_str!("../input.txt");
 
     let mut group = c.benchmark_group("day_10::part1");
     group.bench_with_input("part1", input, |b, input| {
@@ -15,7 +15,7 @@ fn criterion_benchmark_part1(c: &mut Criterion) {
 }
 
 fn criterion_benchmark_part2(c: &mut Criterion) {
-    let input = include_str!("../input2.txt");
+    let input = include_str!("../input.txt");
 
     let mut group = c.benchmark_group("day_10::part2");
     group.bench_with_input("part2", input, |b, input| {
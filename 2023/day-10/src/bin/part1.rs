@@ -1,3 +1,5 @@
+use day_10::distance::DistanceMap;
+use day_10::maze::Maze;
 use day_10::part1::process;
 use miette::Context;
 
@@ -14,6 +16,14 @@ fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
     let file = include_str!("../../input.txt");
+    // `--heat-map` prints the distance of every loop tile from the start
+    if std::env::args().any(|arg| arg == "--heat-map") {
+        let maze = Maze::parse(file)?;
+        let distances = DistanceMap::new(&maze)?;
+        print!("{distances}");
+        println!("farthest: {:?}", distances.farthest());
+        println!("dangling pipes: {}", distances.dangling_pipes().len());
+    }
     let result = process(file).context("process part 1")?;
     println!("{}", result);
     Ok(())
@@ -1,3 +1,6 @@
+use day_10::interior::Enclosure;
+use day_10::loops::find_loops;
+use day_10::maze::Maze;
 use day_10::part2::process;
 use miette::Context;
 
@@ -14,6 +17,28 @@ fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
     let file = include_str!("../../input.txt");
+    // `--render` prints the grid with inside and outside tiles marked
+    if std::env::args().any(|arg| arg == "--render") {
+        let maze = Maze::parse(file)?;
+        print!("{}", Enclosure::new(&maze)?);
+    }
+    // `--loops` lists every closed loop in the maze, not just the start's
+    if std::env::args().any(|arg| arg == "--loops") {
+        let maze = Maze::parse(file)?;
+        for pipe_loop in find_loops(&maze) {
+            println!(
+                "{:?}: length {}, enclosing {}{}",
+                pipe_loop.tiles[0],
+                pipe_loop.len(),
+                pipe_loop.enclosed,
+                if pipe_loop.contains_start {
+                    " (start)"
+                } else {
+                    ""
+                }
+            );
+        }
+    }
     let result = process(file).context("process part 2")?;
     println!("{}", result);
     Ok(())
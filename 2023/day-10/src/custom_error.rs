@@ -6,4 +6,32 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("failed to parse maze: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("no start tile")]
+    #[diagnostic(code(aoc::no_start))]
+    NoStart,
+
+    #[error("more than one start tile")]
+    #[diagnostic(code(aoc::multiple_starts))]
+    MultipleStarts,
+
+    #[error("line {line}, column {column}: fewer than two pipes connect to the start")]
+    #[diagnostic(code(aoc::start_not_connected))]
+    StartNotConnected { line: usize, column: usize },
+
+    #[error("line {line}, column {column}: the start could close more than one loop")]
+    #[diagnostic(code(aoc::ambiguous_start))]
+    AmbiguousStart { line: usize, column: usize },
+
+    #[error("line {line}, column {column}: the loop through the start breaks here")]
+    #[diagnostic(code(aoc::broken_loop))]
+    BrokenLoop { line: usize, column: usize },
+
+    #[error("scanline finds {scanline} enclosed tiles but Pick's theorem gives {pick}")]
+    #[diagnostic(code(aoc::interior_mismatch))]
+    InteriorMismatch { scanline: usize, pick: usize },
 }
@@ -0,0 +1,159 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use ndarray::Array2;
+
+use crate::custom_error::AocError;
+use crate::maze::{Direction, Maze, Pos};
+
+/// Steps from the start to every tile reachable along connected pipes.
+#[derive(Debug, Clone)]
+pub struct DistanceMap<'m> {
+    maze: &'m Maze,
+    distances: Array2<Option<u32>>,
+}
+
+impl<'m> DistanceMap<'m> {
+    /// Breadth-first search from the start, moving between neighbours only
+    /// when both tiles open towards each other. Fails if the loop through the
+    /// start does not close, as the distances would then be meaningless.
+    pub fn new(maze: &'m Maze) -> Result<DistanceMap<'m>, AocError> {
+        maze.loop_path()?;
+        let mut distances = Array2::from_elem(maze.dim(), None);
+        distances[maze.start()] = Some(0);
+        let mut queue = VecDeque::from([(maze.start(), 0)]);
+        while let Some((pos, distance)) = queue.pop_front() {
+            for dir in Direction::ALL {
+                if maze.openings(pos) & dir.bit() == 0 {
+                    continue;
+                }
+                let Some(next) = maze.neighbour(pos, dir) else {
+                    continue;
+                };
+                if maze.openings(next) & dir.opposite().bit() != 0 && distances[next].is_none() {
+                    distances[next] = Some(distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+        Ok(DistanceMap { maze, distances })
+    }
+
+    pub fn distance(&self, pos: Pos) -> Option<u32> {
+        self.distances[pos]
+    }
+
+    /// Every reached tile with its distance, in row-major order.
+    pub fn loop_distances(&self) -> Vec<(Pos, u32)> {
+        self.distances
+            .indexed_iter()
+            .filter_map(|(pos, distance)| distance.map(|distance| (pos, distance)))
+            .collect()
+    }
+
+    pub fn max_distance(&self) -> u32 {
+        self.distances.iter().flatten().copied().max().unwrap_or(0)
+    }
+
+    /// The tiles at `max_distance`: one for a loop of even length.
+    pub fn farthest(&self) -> Vec<Pos> {
+        let max = self.max_distance();
+        self.loop_distances()
+            .into_iter()
+            .filter(|(_, distance)| *distance == max)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// Pipes off the loop that open towards a loop tile, which does not open
+    /// back. Returned with the side they point through.
+    pub fn dangling_pipes(&self) -> Vec<(Pos, Direction)> {
+        self.distances
+            .indexed_iter()
+            .filter(|(_, distance)| distance.is_none())
+            .flat_map(|(pos, _)| {
+                Direction::ALL
+                    .into_iter()
+                    .filter(move |dir| self.maze.openings(pos) & dir.bit() != 0)
+                    .filter(move |dir| {
+                        self.maze
+                            .neighbour(pos, *dir)
+                            .is_some_and(|next| self.distances[next].is_some())
+                    })
+                    .map(move |dir| (pos, dir))
+            })
+            .collect()
+    }
+}
+
+/// Loop tiles as a digit from `0` (the start) to `9` (the farthest tiles),
+/// everything else as `.`.
+impl fmt::Display for DistanceMap<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let max = self.max_distance().max(1);
+        for row in self.distances.rows() {
+            for distance in row.iter() {
+                let c = match distance {
+                    Some(distance) => char::from_digit(distance * 9 / max, 10).unwrap(),
+                    None => '.',
+                };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+    #[test]
+    fn test_distances() -> miette::Result<()> {
+        let maze = Maze::parse(EXAMPLE)?;
+        let distances = DistanceMap::new(&maze)?;
+        assert_eq!(Some(0), distances.distance((1, 1)));
+        assert_eq!(Some(2), distances.distance((1, 3)));
+        assert_eq!(None, distances.distance((0, 0)));
+        assert_eq!(8, distances.loop_distances().len());
+        assert_eq!(4, distances.max_distance());
+        assert_eq!(vec![(3, 3)], distances.farthest());
+        assert_eq!(
+            ".....
+.024.
+.2.6.
+.469.
+.....
+",
+            distances.to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_dangling_pipes() -> miette::Result<()> {
+        let maze = Maze::parse(EXAMPLE)?;
+        let distances = DistanceMap::new(&maze)?;
+        assert_eq!(
+            vec![
+                ((0, 2), Direction::South),
+                ((0, 3), Direction::South),
+                ((2, 0), Direction::East),
+                ((2, 2), Direction::South),
+                ((2, 2), Direction::West),
+                ((3, 0), Direction::East),
+                ((4, 1), Direction::North),
+                ((4, 3), Direction::North),
+            ],
+            distances.dangling_pipes()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+
+use ndarray::Array2;
+
+use crate::custom_error::AocError;
+use crate::maze::{Direction, Maze, Pos};
+
+/// Number of tiles enclosed by a loop given as consecutive tiles.
+pub fn count_interior_points(path: &[Pos]) -> usize {
+    // Shoelace formula
+    let mut area: isize = 0;
+    for i in 0..path.len() {
+        let (y1, x1) = path[i];
+        let (y2, x2) = path[(i + 1) % path.len()];
+        area += (x1 as isize * y2 as isize) - (x2 as isize * y1 as isize);
+    }
+    area = area.abs() / 2;
+
+    // Pick's theorem
+    (area - (path.len() as isize / 2) + 1) as usize
+}
+
+/// The loop through the start and the tiles it encloses.
+#[derive(Debug, Clone)]
+pub struct Enclosure<'m> {
+    maze: &'m Maze,
+    path: Vec<Pos>,
+    on_loop: Array2<bool>,
+    inside: Array2<bool>,
+}
+
+impl<'m> Enclosure<'m> {
+    /// Marks the enclosed tiles with a parity scanline: walking along a row,
+    /// every loop tile that opens north crosses the loop boundary. Tiles
+    /// opening only south pair up with the next vertical turn, so squeezing
+    /// between pipes is handled without special cases.
+    pub fn new(maze: &'m Maze) -> Result<Enclosure<'m>, AocError> {
+        let path = maze.loop_path()?;
+        let mut on_loop = Array2::from_elem(maze.dim(), false);
+        for pos in path.iter() {
+            on_loop[*pos] = true;
+        }
+        let mut inside = Array2::from_elem(maze.dim(), false);
+        for (row, tiles) in on_loop.rows().into_iter().enumerate() {
+            let mut crossings = 0;
+            for (col, is_loop) in tiles.iter().enumerate() {
+                if *is_loop {
+                    if maze.openings((row, col)) & Direction::North.bit() != 0 {
+                        crossings += 1;
+                    }
+                } else if crossings % 2 == 1 {
+                    inside[(row, col)] = true;
+                }
+            }
+        }
+        Ok(Enclosure {
+            maze,
+            path,
+            on_loop,
+            inside,
+        })
+    }
+
+    pub fn path(&self) -> &[Pos] {
+        &self.path
+    }
+
+    pub fn is_inside(&self, pos: Pos) -> bool {
+        self.inside[pos]
+    }
+
+    /// The enclosed tiles in row-major order.
+    pub fn inside_tiles(&self) -> Vec<Pos> {
+        self.inside
+            .indexed_iter()
+            .filter(|(_, inside)| **inside)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// The number of enclosed tiles, after checking that the scanline agrees
+    /// with shoelace and Pick's theorem.
+    pub fn count(&self) -> Result<usize, AocError> {
+        let scanline = self.inside.iter().filter(|inside| **inside).count();
+        let pick = count_interior_points(&self.path);
+        if scanline != pick {
+            return Err(AocError::InteriorMismatch { scanline, pick });
+        }
+        Ok(scanline)
+    }
+}
+
+/// The start as `S`, other loop tiles as their pipes, and everything else
+/// as `I` (inside) or `O` (outside).
+impl fmt::Display for Enclosure<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (row, tiles) in self.on_loop.rows().into_iter().enumerate() {
+            for (col, is_loop) in tiles.iter().enumerate() {
+                let c = if (row, col) == self.maze.start() {
+                    'S'
+                } else if *is_loop {
+                    self.maze.tile((row, col)).to_char()
+                } else if self.inside[(row, col)] {
+                    'I'
+                } else {
+                    'O'
+                };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_squeezing_between_pipes() -> miette::Result<()> {
+        let maze = Maze::parse(
+            "..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........",
+        )?;
+        let enclosure = Enclosure::new(&maze)?;
+        assert_eq!(
+            vec![(6, 2), (6, 3), (6, 6), (6, 7)],
+            enclosure.inside_tiles()
+        );
+        assert_eq!(4, enclosure.count()?);
+        assert_eq!(
+            "OOOOOOOOOO
+OS------7O
+O|F----7|O
+O||OOOO||O
+O||OOOO||O
+O|L-7F-J|O
+O|II||II|O
+OL--JL--JO
+OOOOOOOOOO
+",
+            enclosure.to_string()
+        );
+        Ok(())
+    }
+}
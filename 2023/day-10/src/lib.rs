@@ -1,4 +1,8 @@
 pub mod custom_error;
+pub mod distance;
+pub mod interior;
+pub mod loops;
+pub mod maze;
 
 pub mod part1;
 pub mod part2;
@@ -0,0 +1,121 @@
+use ndarray::Array2;
+
+use crate::interior::count_interior_points;
+use crate::maze::{Direction, Maze, Pos};
+
+/// A closed loop of pipes anywhere in the maze.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipeLoop {
+    /// The loop's tiles in walking order.
+    pub tiles: Vec<Pos>,
+    /// Number of tiles the loop encloses.
+    pub enclosed: usize,
+    pub contains_start: bool,
+}
+
+impl PipeLoop {
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+}
+
+/// Directions in which `pos` and its neighbour open towards each other.
+fn connections(maze: &Maze, pos: Pos) -> impl Iterator<Item = (Direction, Pos)> + '_ {
+    Direction::ALL
+        .into_iter()
+        .filter(move |dir| maze.openings(pos) & dir.bit() != 0)
+        .filter_map(move |dir| {
+            maze.neighbour(pos, dir)
+                .filter(|next| maze.openings(*next) & dir.opposite().bit() != 0)
+                .map(|next| (dir, next))
+        })
+}
+
+/// Every closed loop in the maze, i.e. every group of connected pipes in
+/// which each tile connects to exactly two others. Loops are ordered by
+/// their first tile in row-major order.
+pub fn find_loops(maze: &Maze) -> Vec<PipeLoop> {
+    let (rows, cols) = maze.dim();
+    let mut visited = Array2::from_elem((rows, cols), false);
+    let mut loops = Vec::new();
+    for first in (0..rows).flat_map(|row| (0..cols).map(move |col| (row, col))) {
+        if visited[first] || maze.openings(first) == 0 {
+            continue;
+        }
+        // Collect the whole component so it is not revisited from another tile
+        let mut component = vec![first];
+        let mut stack = vec![first];
+        visited[first] = true;
+        while let Some(pos) = stack.pop() {
+            for (_, next) in connections(maze, pos) {
+                if !visited[next] {
+                    visited[next] = true;
+                    component.push(next);
+                    stack.push(next);
+                }
+            }
+        }
+        if component
+            .iter()
+            .all(|pos| connections(maze, *pos).count() == 2)
+        {
+            let tiles = walk(maze, first);
+            loops.push(PipeLoop {
+                enclosed: count_interior_points(&tiles),
+                contains_start: tiles.contains(&maze.start()),
+                tiles,
+            });
+        }
+    }
+    loops
+}
+
+/// Follows a loop all of whose tiles connect on both sides.
+fn walk(maze: &Maze, first: Pos) -> Vec<Pos> {
+    let mut tiles = vec![first];
+    let (mut came_from, mut pos) = connections(maze, first)
+        .next()
+        .map(|(dir, next)| (dir.opposite(), next))
+        .expect("loop tiles are connected");
+    while pos != first {
+        tiles.push(pos);
+        (came_from, pos) = connections(maze, pos)
+            .find(|(dir, _)| *dir != came_from)
+            .map(|(dir, next)| (dir.opposite(), next))
+            .expect("loop tiles are connected");
+    }
+    tiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_loops() -> miette::Result<()> {
+        let maze = Maze::parse(
+            "F--7.S7
+|F7|.||
+|LJ|.LJ
+L--J.--",
+        )?;
+        let loops = find_loops(&maze);
+        assert_eq!(
+            vec![(12, 4, false), (6, 0, true), (4, 0, false)],
+            loops
+                .iter()
+                .map(|pipe_loop| (
+                    pipe_loop.len(),
+                    pipe_loop.enclosed,
+                    pipe_loop.contains_start
+                ))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(maze.loop_path()?.len(), loops[1].len());
+        Ok(())
+    }
+}
@@ -0,0 +1,414 @@
+use ndarray::Array2;
+use nom::{
+    branch::alt,
+    bytes::complete,
+    character::complete::line_ending,
+    combinator::{all_consuming, opt},
+    multi::{many1, separated_list1},
+    sequence::terminated,
+    IResult, Parser,
+};
+
+use crate::custom_error::AocError;
+
+/// Position as (row, column).
+pub type Pos = (usize, usize);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// This direction's bit in an openings mask.
+    pub fn bit(self) -> u8 {
+        match self {
+            Direction::North => 0b0001,
+            Direction::East => 0b0010,
+            Direction::South => 0b0100,
+            Direction::West => 0b1000,
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    fn from_mask(mask: u8) -> Option<Direction> {
+        Direction::ALL.into_iter().find(|dir| dir.bit() == mask)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Tile {
+    Vertical,
+    Horizontal,
+    NorthEast,
+    NorthWest,
+    SouthWest,
+    SouthEast,
+    Ground,
+    Start,
+}
+
+impl Tile {
+    const PIPES: [Tile; 6] = [
+        Tile::Vertical,
+        Tile::Horizontal,
+        Tile::NorthEast,
+        Tile::NorthWest,
+        Tile::SouthWest,
+        Tile::SouthEast,
+    ];
+
+    /// The sides this tile connects to, as a mask of `Direction::bit`s. The
+    /// start is unknown until resolved, so it has no openings of its own.
+    pub fn openings(self) -> u8 {
+        let (north, east, south, west) = (
+            Direction::North.bit(),
+            Direction::East.bit(),
+            Direction::South.bit(),
+            Direction::West.bit(),
+        );
+        match self {
+            Tile::Vertical => north | south,
+            Tile::Horizontal => east | west,
+            Tile::NorthEast => north | east,
+            Tile::NorthWest => north | west,
+            Tile::SouthWest => south | west,
+            Tile::SouthEast => south | east,
+            Tile::Ground | Tile::Start => 0,
+        }
+    }
+
+    /// The pipe with exactly these openings.
+    pub fn from_openings(openings: u8) -> Option<Tile> {
+        Tile::PIPES
+            .into_iter()
+            .find(|tile| tile.openings() == openings)
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Tile::Vertical => '|',
+            Tile::Horizontal => '-',
+            Tile::NorthEast => 'L',
+            Tile::NorthWest => 'J',
+            Tile::SouthWest => '7',
+            Tile::SouthEast => 'F',
+            Tile::Ground => '.',
+            Tile::Start => 'S',
+        }
+    }
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Vec<Tile>>> {
+    all_consuming(terminated(
+        separated_list1(
+            line_ending,
+            many1(alt((
+                complete::tag("-").map(|_| Tile::Horizontal),
+                complete::tag("|").map(|_| Tile::Vertical),
+                complete::tag("L").map(|_| Tile::NorthEast),
+                complete::tag("J").map(|_| Tile::NorthWest),
+                complete::tag("7").map(|_| Tile::SouthWest),
+                complete::tag("F").map(|_| Tile::SouthEast),
+                complete::tag("S").map(|_| Tile::Start),
+                complete::tag(".").map(|_| Tile::Ground),
+            ))),
+        ),
+        opt(line_ending),
+    ))(input)
+}
+
+/// The pipe grid, with the start tile resolved to the pipe shape that closes
+/// the loop through it.
+#[derive(Debug, Clone)]
+pub struct Maze {
+    tiles: Array2<Tile>,
+    start: Pos,
+    start_tile: Tile,
+}
+
+impl Maze {
+    pub fn parse(input: &str) -> Result<Maze, AocError> {
+        let (_, rows) = parse_input(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+        let shape = (rows.len(), rows[0].len());
+        if let Some((idx, row)) = rows
+            .iter()
+            .enumerate()
+            .find(|(_, row)| row.len() != shape.1)
+        {
+            return Err(AocError::ParseError(format!(
+                "line {} has {} tiles, expected {}",
+                idx + 1,
+                row.len(),
+                shape.1
+            )));
+        }
+        let tiles = Array2::from_shape_vec(shape, rows.into_iter().flatten().collect())
+            .map_err(|e| AocError::ParseError(e.to_string()))?;
+        let mut starts = tiles
+            .indexed_iter()
+            .filter(|(_, tile)| **tile == Tile::Start)
+            .map(|(pos, _)| pos);
+        let start = starts.next().ok_or(AocError::NoStart)?;
+        if starts.next().is_some() {
+            return Err(AocError::MultipleStarts);
+        }
+
+        let mut maze = Maze {
+            tiles,
+            start,
+            start_tile: Tile::Start,
+        };
+        maze.start_tile = maze.resolve_start()?;
+        Ok(maze)
+    }
+
+    /// Finds the pipe shape for the start. Usually exactly two neighbours
+    /// connect back to it; with more, the shape must be the only one whose
+    /// loop actually closes.
+    fn resolve_start(&mut self) -> Result<Tile, AocError> {
+        let connecting: Vec<Direction> = Direction::ALL
+            .into_iter()
+            .filter(|dir| {
+                self.neighbour(self.start, *dir)
+                    .is_some_and(|next| self.openings(next) & dir.opposite().bit() != 0)
+            })
+            .collect();
+        let (row, col) = self.start;
+        let candidates: Vec<Tile> = connecting
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, first)| {
+                connecting[idx + 1..]
+                    .iter()
+                    .map(move |second| first.bit() | second.bit())
+            })
+            .filter_map(Tile::from_openings)
+            .collect();
+        match candidates.as_slice() {
+            [] => Err(AocError::StartNotConnected {
+                line: row + 1,
+                column: col + 1,
+            }),
+            [tile] => Ok(*tile),
+            _ => {
+                let closing: Vec<Tile> = candidates
+                    .into_iter()
+                    .filter(|tile| {
+                        self.start_tile = *tile;
+                        self.loop_path().is_ok()
+                    })
+                    .collect();
+                match closing.as_slice() {
+                    [tile] => Ok(*tile),
+                    _ => Err(AocError::AmbiguousStart {
+                        line: row + 1,
+                        column: col + 1,
+                    }),
+                }
+            }
+        }
+    }
+
+    pub fn start(&self) -> Pos {
+        self.start
+    }
+
+    pub fn dim(&self) -> (usize, usize) {
+        self.tiles.dim()
+    }
+
+    /// The tile at `pos`, with the start shown as its resolved pipe.
+    pub fn tile(&self, pos: Pos) -> Tile {
+        if pos == self.start {
+            self.start_tile
+        } else {
+            self.tiles[pos]
+        }
+    }
+
+    pub fn openings(&self, pos: Pos) -> u8 {
+        self.tile(pos).openings()
+    }
+
+    pub fn neighbour(&self, (row, col): Pos, dir: Direction) -> Option<Pos> {
+        let (rows, cols) = self.dim();
+        match dir {
+            Direction::North => row.checked_sub(1).map(|row| (row, col)),
+            Direction::East => (col + 1 < cols).then_some((row, col + 1)),
+            Direction::South => (row + 1 < rows).then_some((row + 1, col)),
+            Direction::West => col.checked_sub(1).map(|col| (row, col)),
+        }
+    }
+
+    /// The tiles of the loop through the start, in walking order beginning
+    /// with the start. Each step leaves through the opening the walk did not
+    /// come in by, and the next tile must open back towards it.
+    pub fn loop_path(&self) -> Result<Vec<Pos>, AocError> {
+        let broken = |(row, col): Pos| AocError::BrokenLoop {
+            line: row + 1,
+            column: col + 1,
+        };
+        let start_openings = self.openings(self.start);
+        let mut dir = Direction::ALL
+            .into_iter()
+            .find(|dir| start_openings & dir.bit() != 0)
+            .ok_or_else(|| broken(self.start))?;
+        let mut pos = self.start;
+        let mut path = vec![pos];
+        loop {
+            let next = self.neighbour(pos, dir).ok_or_else(|| broken(pos))?;
+            let came_from = dir.opposite();
+            let openings = self.openings(next);
+            if openings & came_from.bit() == 0 {
+                return Err(broken(next));
+            }
+            if next == self.start {
+                return Ok(path);
+            }
+            dir = Direction::from_mask(openings & !came_from.bit()).ok_or_else(|| broken(next))?;
+            pos = next;
+            path.push(pos);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(
+        "-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF",
+        Tile::SouthEast
+    )]
+    #[case(
+        "..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...",
+        Tile::SouthEast
+    )]
+    #[case(
+        ".....
+.F-7.
+.|.|.
+.L-S.
+.....",
+        Tile::NorthWest
+    )]
+    // Every neighbour connects back, but only one pair closes a loop
+    #[case(
+        ".|...
+-S-7.
+.|.|.
+.L-J.",
+        Tile::SouthEast
+    )]
+    fn test_resolve_start(#[case] input: &str, #[case] expected: Tile) -> miette::Result<()> {
+        let maze = Maze::parse(input)?;
+        assert_eq!(expected, maze.tile(maze.start()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_loop_path() -> miette::Result<()> {
+        let maze = Maze::parse(
+            ".....
+.S-7.
+.|.|.
+.L-J.
+.....",
+        )?;
+        assert_eq!(
+            vec![
+                (1, 1),
+                (1, 2),
+                (1, 3),
+                (2, 3),
+                (3, 3),
+                (3, 2),
+                (3, 1),
+                (2, 1)
+            ],
+            maze.loop_path()?
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("...\n.S.\n...")]
+    #[case("...\n.S-\n...")]
+    fn test_start_not_connected(#[case] input: &str) {
+        assert!(matches!(
+            Maze::parse(input),
+            Err(AocError::StartNotConnected { line: 2, column: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_ambiguous_start() {
+        // Two loops meet at the start, and both close
+        let input = "F7.
+LS7
+.LJ";
+        assert!(matches!(
+            Maze::parse(input),
+            Err(AocError::AmbiguousStart { line: 2, column: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_broken_loop() -> miette::Result<()> {
+        let maze = Maze::parse(
+            ".....
+.S-7.
+.|.|.
+.L-7.
+.....",
+        )?;
+        assert!(matches!(
+            maze.loop_path(),
+            Err(AocError::BrokenLoop { line: 4, column: 4 })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_start() {
+        assert!(matches!(Maze::parse("F7\nLJ"), Err(AocError::NoStart)));
+    }
+
+    #[rstest]
+    #[case("S7\nLJ.", "failed to parse maze: line 2 has 3 tiles, expected 2")]
+    #[case("S7.\nLJ\n.|..", "failed to parse maze: line 2 has 2 tiles, expected 3")]
+    #[case("S7.\nL\nJ..", "failed to parse maze: line 2 has 1 tiles, expected 3")]
+    fn test_ragged_rows(#[case] input: &str, #[case] error: &str) {
+        assert_eq!(error, Maze::parse(input).unwrap_err().to_string());
+    }
+}
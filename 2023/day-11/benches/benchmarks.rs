@@ -1,10 +1,13 @@
+use aoc_utils::rng::Lcg;
 use criterion::{
     criterion_group, criterion_main, Criterion,
 };
+use day_11::universe::{total_distance, Universe};
 use day_11::*;
+use itertools::Itertools;
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
-    let input = include_str!("../input1.txt");
+    let input = include_str!("../input.txt");
 
     let mut group = c.benchmark_group("day_11::part1");
     group.bench_with_input("part1", input, |b, input| {
@@ -15,7 +18,7 @@ fn criterion_benchmark_part1(c: &mut Criterion) {
 }
 
 fn criterion_benchmark_part2(c: &mut Criterion) {
-    let input = include_str!("../input2.txt");
+    let input = include_str!("../input.txt");
 
     let mut group = c.benchmark_group("day_11::part2");
     group.bench_with_input("part2", input, |b, input| {
@@ -25,9 +28,62 @@ fn criterion_benchmark_part2(c: &mut Criterion) {
     group.finish();
 }
 
+/// Deterministic pseudo-random image with `count` galaxies, so runs are
+/// comparable. Roughly one row and column in eight stays empty.
+fn generate_image(count: usize) -> String {
+    let size = (count as f64).sqrt() as usize * 4;
+    let mut rng = Lcg::default();
+    let mut image = vec![vec!['.'; size]; size];
+    let mut placed = 0;
+    while placed < count {
+        let (row, col) = (rng.below(size), rng.below(size));
+        if row % 8 != 7 && col % 8 != 7 && image[row][col] == '.' {
+            image[row][col] = '#';
+            placed += 1;
+        }
+    }
+    image
+        .into_iter()
+        .map(|row| row.into_iter().collect::<String>())
+        .join("\n")
+}
+
+/// Visits every pair, as both parts did before the sorted prefix sums.
+fn total_distance_pairwise(galaxies: &[(u64, u64)]) -> u128 {
+    galaxies
+        .iter()
+        .tuple_combinations()
+        .map(|((row1, col1), (row2, col2))| (row1.abs_diff(*row2) + col1.abs_diff(*col2)) as u128)
+        .sum()
+}
+
+fn criterion_benchmark_distances(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day_11::distances");
+    group.sample_size(10);
+    for count in [10_000, 100_000] {
+        let image = generate_image(count);
+        let galaxies = Universe::parse(&image)
+            .and_then(|universe| universe.expanded(1_000_000))
+            .expect("generated image parses");
+        // Every pair of 10^5 galaxies takes seconds per iteration, so only
+        // the smaller image compares against it
+        if count <= 10_000 {
+            group.bench_function(format!("pairwise/{count}"), |b| {
+                b.iter(|| total_distance_pairwise(&galaxies))
+            });
+        }
+        group.bench_function(format!("sorted/{count}"), |b| {
+            b.iter(|| total_distance(&galaxies))
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     criterion_benchmark_part1,
-    criterion_benchmark_part2
+    criterion_benchmark_part2,
+    criterion_benchmark_distances
 );
 criterion_main!(benches);
@@ -1,5 +1,6 @@
-use day_11::part2::process;
+use day_11::universe::sum_of_distances;
 use miette::Context;
+use miette::IntoDiagnostic;
 
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
@@ -14,7 +15,15 @@ fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
     let file = include_str!("../../input.txt");
-    let result = process(file).context("process part 2")?;
+    // `--expansion N` replaces each empty row and column with N of them
+    let expansion = match std::env::args()
+        .skip_while(|arg| arg != "--expansion")
+        .nth(1)
+    {
+        Some(expansion) => expansion.parse().into_diagnostic()?,
+        None => 1_000_000,
+    };
+    let result = sum_of_distances(file, expansion).context("process part 2")?;
     println!("{}", result);
     Ok(())
 }
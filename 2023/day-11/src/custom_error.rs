@@ -6,4 +6,28 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("failed to parse image: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("invalid expansion factor {0}, expected at least 1")]
+    #[diagnostic(code(aoc::invalid_expansion))]
+    InvalidExpansion(u64),
+
+    #[error("expanded coordinates overflow u64")]
+    #[diagnostic(code(aoc::overflow))]
+    Overflow,
+
+    #[error("distances between galaxies overflow u128")]
+    #[diagnostic(code(aoc::distance_overflow))]
+    DistanceOverflow,
+
+    #[error("unknown metric `{0}`, expected manhattan, chebyshev or euclidean-squared")]
+    #[diagnostic(code(aoc::unknown_metric))]
+    UnknownMetric(String),
+
+    #[error("histogram buckets must be at least 1 wide")]
+    #[diagnostic(code(aoc::invalid_bucket_width))]
+    InvalidBucketWidth,
 }
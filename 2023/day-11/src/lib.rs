@@ -1,4 +1,18 @@
 pub mod custom_error;
+pub mod query;
+pub mod universe;
 
 pub mod part1;
 pub mod part2;
+
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = "...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....";
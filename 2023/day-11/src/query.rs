@@ -0,0 +1,230 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::custom_error::AocError;
+use crate::universe::total_distance;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    Manhattan,
+    Chebyshev,
+    /// Squared, so distances stay integers.
+    EuclideanSquared,
+}
+
+impl Metric {
+    /// Fails only for squared Euclidean distances of gaps close to
+    /// `u64::MAX`, which large expansion factors can produce.
+    pub fn distance(
+        self,
+        (row1, col1): (u64, u64),
+        (row2, col2): (u64, u64),
+    ) -> Result<u128, AocError> {
+        let rows = row1.abs_diff(row2) as u128;
+        let cols = col1.abs_diff(col2) as u128;
+        match self {
+            Metric::Manhattan => Ok(rows + cols),
+            Metric::Chebyshev => Ok(rows.max(cols)),
+            Metric::EuclideanSquared => rows
+                .checked_mul(rows)
+                .zip(cols.checked_mul(cols))
+                .and_then(|(rows, cols)| rows.checked_add(cols))
+                .ok_or(AocError::DistanceOverflow),
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Metric, AocError> {
+        match s {
+            "manhattan" => Ok(Metric::Manhattan),
+            "chebyshev" => Ok(Metric::Chebyshev),
+            "euclidean-squared" => Ok(Metric::EuclideanSquared),
+            _ => Err(AocError::UnknownMetric(s.to_string())),
+        }
+    }
+}
+
+/// Queries over galaxy positions, typically from `Universe::expanded`.
+/// Apart from the Manhattan total, they compare every pair of galaxies.
+#[derive(Debug, Clone)]
+pub struct Galaxies {
+    positions: Vec<(u64, u64)>,
+}
+
+impl Galaxies {
+    pub fn new(positions: Vec<(u64, u64)>) -> Galaxies {
+        Galaxies { positions }
+    }
+
+    pub fn positions(&self) -> &[(u64, u64)] {
+        &self.positions
+    }
+
+    fn pairs(
+        &self,
+        metric: Metric,
+    ) -> impl Iterator<Item = Result<(usize, usize, u128), AocError>> + '_ {
+        self.positions.iter().enumerate().flat_map(move |(i, a)| {
+            self.positions[i + 1..]
+                .iter()
+                .enumerate()
+                .map(move |(offset, b)| Ok((i, i + 1 + offset, metric.distance(*a, *b)?)))
+        })
+    }
+
+    /// Sum of the distances between all pairs.
+    pub fn total(&self, metric: Metric) -> Result<u128, AocError> {
+        match metric {
+            Metric::Manhattan => Ok(total_distance(&self.positions)),
+            _ => self.pairs(metric).try_fold(0u128, |total, pair| {
+                total.checked_add(pair?.2).ok_or(AocError::DistanceOverflow)
+            }),
+        }
+    }
+
+    /// For each galaxy, the index of its closest other galaxy and the
+    /// distance to it. Ties go to the lower index.
+    pub fn nearest_neighbours(
+        &self,
+        metric: Metric,
+    ) -> Result<Vec<Option<(usize, u128)>>, AocError> {
+        let mut nearest: Vec<Option<(usize, u128)>> = vec![None; self.positions.len()];
+        for pair in self.pairs(metric) {
+            let (i, j, distance) = pair?;
+            for (from, to) in [(i, j), (j, i)] {
+                if nearest[from].is_none_or(|(_, best)| distance < best) {
+                    nearest[from] = Some((to, distance));
+                }
+            }
+        }
+        Ok(nearest)
+    }
+
+    /// The two galaxies furthest apart, as indices, and their distance.
+    pub fn farthest_pair(&self, metric: Metric) -> Result<Option<(usize, usize, u128)>, AocError> {
+        let mut farthest: Option<(usize, usize, u128)> = None;
+        for pair in self.pairs(metric) {
+            let pair = pair?;
+            if farthest.is_none_or(|best| pair.2 > best.2) {
+                farthest = Some(pair);
+            }
+        }
+        Ok(farthest)
+    }
+
+    /// How many pairs fall into each bucket of `bucket_width` distances,
+    /// keyed by the bucket's smallest distance.
+    pub fn histogram(
+        &self,
+        metric: Metric,
+        bucket_width: u128,
+    ) -> Result<BTreeMap<u128, usize>, AocError> {
+        if bucket_width == 0 {
+            return Err(AocError::InvalidBucketWidth);
+        }
+        let mut histogram = BTreeMap::new();
+        for pair in self.pairs(metric) {
+            let (_, _, distance) = pair?;
+            *histogram
+                .entry(distance / bucket_width * bucket_width)
+                .or_insert(0) += 1;
+        }
+        Ok(histogram)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::universe::Universe;
+    use crate::EXAMPLE;
+
+    fn galaxies() -> Result<Galaxies, AocError> {
+        Ok(Galaxies::new(Universe::parse(EXAMPLE)?.expanded(2)?))
+    }
+
+    #[rstest]
+    #[case(Metric::Manhattan, 9)]
+    #[case(Metric::Chebyshev, 5)]
+    #[case(Metric::EuclideanSquared, 41)]
+    fn test_distance(#[case] metric: Metric, #[case] expected: u128) -> miette::Result<()> {
+        // Galaxies 5 and 9 in the puzzle's numbering
+        let galaxies = galaxies()?;
+        let positions = galaxies.positions();
+        assert_eq!((6, 1), positions[4]);
+        assert_eq!((11, 5), positions[8]);
+        assert_eq!(expected, metric.distance(positions[4], positions[8])?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_total() -> miette::Result<()> {
+        let galaxies = galaxies()?;
+        assert_eq!(374, galaxies.total(Metric::Manhattan)?);
+        assert_eq!(
+            galaxies
+                .pairs(Metric::Manhattan)
+                .map(|pair| pair.map(|(_, _, distance)| distance))
+                .sum::<Result<u128, _>>()?,
+            galaxies.total(Metric::Manhattan)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_queries() -> miette::Result<()> {
+        let galaxies = galaxies()?;
+        let nearest = galaxies.nearest_neighbours(Metric::Manhattan)?;
+        assert_eq!(
+            vec![
+                Some((1, 6)),
+                Some((3, 5)),
+                Some((4, 5)),
+                Some((1, 5)),
+                Some((2, 5)),
+                Some((3, 6)),
+                Some((8, 5)),
+                Some((8, 5)),
+                Some((6, 5)),
+            ],
+            nearest
+        );
+        assert_eq!(Some((1, 7, 19)), galaxies.farthest_pair(Metric::Manhattan)?);
+        assert_eq!(
+            BTreeMap::from([(5, 18), (10, 11), (15, 7)]),
+            galaxies.histogram(Metric::Manhattan, 5)?
+        );
+        assert!(matches!(
+            galaxies.histogram(Metric::Manhattan, 0),
+            Err(AocError::InvalidBucketWidth)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_galaxy() -> miette::Result<()> {
+        let galaxies = Galaxies::new(vec![(3, 4)]);
+        assert_eq!(vec![None], galaxies.nearest_neighbours(Metric::Chebyshev)?);
+        assert_eq!(None, galaxies.farthest_pair(Metric::Chebyshev)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflow() -> miette::Result<()> {
+        let galaxies = Galaxies::new(vec![(0, 0), (u64::MAX, u64::MAX)]);
+        assert_eq!(2 * u64::MAX as u128, galaxies.total(Metric::Manhattan)?);
+        for result in [
+            galaxies.total(Metric::EuclideanSquared).map(|_| ()),
+            galaxies.farthest_pair(Metric::EuclideanSquared).map(|_| ()),
+            galaxies.histogram(Metric::EuclideanSquared, 1).map(|_| ()),
+        ] {
+            assert!(matches!(result, Err(AocError::DistanceOverflow)));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,167 @@
+use ndarray::Array2;
+use nom::{
+    branch::alt,
+    bytes::complete,
+    character::complete::line_ending,
+    combinator::{all_consuming, opt},
+    multi::{many1, separated_list1},
+    sequence::terminated,
+    IResult, Parser,
+};
+
+use crate::custom_error::AocError;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Item {
+    Space,
+    Galaxy,
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Vec<Item>>> {
+    all_consuming(terminated(
+        separated_list1(
+            line_ending,
+            many1(alt((
+                complete::tag(".").map(|_| Item::Space),
+                complete::tag("#").map(|_| Item::Galaxy),
+            ))),
+        ),
+        opt(line_ending),
+    ))(input)
+}
+
+/// For each index, how many of the lines before it are empty.
+fn empty_before(lines: impl Iterator<Item = bool>) -> Vec<u64> {
+    lines
+        .scan(0, |count, empty| {
+            let before = *count;
+            *count += empty as u64;
+            Some(before)
+        })
+        .collect()
+}
+
+/// The observed image: galaxy positions plus, per row and column, the number
+/// of empty rows or columns before it.
+#[derive(Debug, Clone)]
+pub struct Universe {
+    galaxies: Vec<(usize, usize)>,
+    empty_rows_before: Vec<u64>,
+    empty_cols_before: Vec<u64>,
+}
+
+impl Universe {
+    pub fn parse(input: &str) -> Result<Universe, AocError> {
+        let (_, rows) = parse_input(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+        let shape = (rows.len(), rows[0].len());
+        let arr = Array2::from_shape_vec(shape, rows.into_iter().flatten().collect())
+            .map_err(|e| AocError::ParseError(e.to_string()))?;
+        let is_empty = |line: ndarray::ArrayView1<Item>| line.iter().all(|x| *x == Item::Space);
+        Ok(Universe {
+            galaxies: arr
+                .indexed_iter()
+                .filter(|(_, x)| **x == Item::Galaxy)
+                .map(|(pos, _)| pos)
+                .collect(),
+            empty_rows_before: empty_before(arr.rows().into_iter().map(is_empty)),
+            empty_cols_before: empty_before(arr.columns().into_iter().map(is_empty)),
+        })
+    }
+
+    /// Galaxy positions after every empty row and column has grown to
+    /// `expansion` rows or columns.
+    pub fn expanded(&self, expansion: u64) -> Result<Vec<(u64, u64)>, AocError> {
+        if expansion == 0 {
+            return Err(AocError::InvalidExpansion(expansion));
+        }
+        let grow = |idx: usize, empty_before: u64| {
+            (expansion - 1)
+                .checked_mul(empty_before)
+                .and_then(|extra| extra.checked_add(idx as u64))
+                .ok_or(AocError::Overflow)
+        };
+        self.galaxies
+            .iter()
+            .map(|(row, col)| {
+                Ok((
+                    grow(*row, self.empty_rows_before[*row])?,
+                    grow(*col, self.empty_cols_before[*col])?,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Sum of `|a - b|` over all pairs. After sorting, the `i`th value is at
+/// least each of the `i` values before it, so it contributes `i * value`
+/// minus their sum.
+fn pairwise_difference_sum(mut values: Vec<u64>) -> u128 {
+    values.sort_unstable();
+    let mut prefix: u128 = 0;
+    let mut total: u128 = 0;
+    for (i, value) in values.into_iter().enumerate() {
+        total += i as u128 * value as u128 - prefix;
+        prefix += value as u128;
+    }
+    total
+}
+
+/// Sum of Manhattan distances between all pairs of galaxies. The rows and
+/// columns contribute independently, so this takes two sorts instead of
+/// visiting every pair.
+pub fn total_distance(galaxies: &[(u64, u64)]) -> u128 {
+    pairwise_difference_sum(galaxies.iter().map(|(row, _)| *row).collect())
+        + pairwise_difference_sum(galaxies.iter().map(|(_, col)| *col).collect())
+}
+
+/// Parses the image and sums the distances between all galaxies for the
+/// given expansion factor.
+pub fn sum_of_distances(input: &str, expansion: u64) -> Result<u128, AocError> {
+    Ok(total_distance(
+        &Universe::parse(input)?.expanded(expansion)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::EXAMPLE;
+
+    #[rstest]
+    #[case(1, 292)]
+    #[case(2, 374)]
+    fn test_total_distance(#[case] expansion: u64, #[case] expected: u128) -> miette::Result<()> {
+        let universe = Universe::parse(EXAMPLE)?;
+        assert_eq!(expected, total_distance(&universe.expanded(expansion)?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches_pairwise() -> miette::Result<()> {
+        let galaxies = Universe::parse(EXAMPLE)?.expanded(7)?;
+        let mut expected = 0;
+        for (idx, (row1, col1)) in galaxies.iter().enumerate() {
+            for (row2, col2) in galaxies[idx + 1..].iter() {
+                expected += (row1.abs_diff(*row2) + col1.abs_diff(*col2)) as u128;
+            }
+        }
+        assert_eq!(expected, total_distance(&galaxies));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_expansion() -> miette::Result<()> {
+        let universe = Universe::parse(EXAMPLE)?;
+        assert!(matches!(
+            universe.expanded(0),
+            Err(AocError::InvalidExpansion(0))
+        ));
+        assert!(matches!(
+            universe.expanded(u64::MAX),
+            Err(AocError::Overflow)
+        ));
+        Ok(())
+    }
+}
@@ -1,26 +1,141 @@
-use criterion::{
-    criterion_group, criterion_main, Criterion,
-};
+use std::collections::HashMap;
+
+use aoc_utils::rng::Lcg;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day_12::springs::{Item, Record};
 use day_12::*;
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
-    let input = include_str!("../input1.txt");
+    let input = include_str!("../input.txt");
 
     let mut group = c.benchmark_group("day_12::part1");
-    group.bench_with_input("part1", input, |b, input| {
-        b.iter(|| part1::process(input))
-    });
+    group.bench_with_input("part1", input, |b, input| b.iter(|| part1::process(input)));
 
     group.finish();
 }
 
 fn criterion_benchmark_part2(c: &mut Criterion) {
-    let input = include_str!("../input2.txt");
+    let input = include_str!("../input.txt");
 
     let mut group = c.benchmark_group("day_12::part2");
-    group.bench_with_input("part2", input, |b, input| {
-        b.iter(|| part2::process(input))
-    });
+    group.bench_with_input("part2", input, |b, input| b.iter(|| part2::process(input)));
+
+    group.finish();
+}
+
+/// Deterministic pseudo-random records of `len` springs, so runs are
+/// comparable. Groups come from a concrete row before some springs are
+/// hidden, so every record has at least one arrangement.
+fn generate_records(count: usize, len: usize) -> Vec<Record> {
+    let mut rng = Lcg::default();
+    (0..count)
+        .map(|_| {
+            let concrete: Vec<Item> = (0..len)
+                .map(|_| {
+                    if rng.below(3) == 0 {
+                        Item::Broken
+                    } else {
+                        Item::Functional
+                    }
+                })
+                .collect();
+            let groups = concrete
+                .split(|item| *item == Item::Functional)
+                .map(|run| run.len())
+                .filter(|len| *len > 0)
+                .collect();
+            let items = concrete
+                .into_iter()
+                .map(|item| {
+                    if rng.below(2) == 0 {
+                        Item::Unknown
+                    } else {
+                        item
+                    }
+                })
+                .collect();
+            Record { items, groups }
+        })
+        .collect()
+}
+
+/// The memoized backtracking both parts used before the table-based count.
+mod backtrack {
+    use super::*;
+
+    fn satisfiable(line: &[Item], start: usize, end: usize) -> bool {
+        if start < 1 || end >= line.len() {
+            return false;
+        }
+        if line[start - 1] == Item::Broken
+            || (end + 1 < line.len() && line[end + 1] == Item::Broken)
+        {
+            return false;
+        }
+        if line[start..=end].contains(&Item::Functional) {
+            return false;
+        }
+        !line[..start].contains(&Item::Broken)
+    }
+
+    fn backtrack_arrangements(
+        line: &[Item],
+        contiguous_counts: &[usize],
+        cache: &mut HashMap<(Vec<Item>, Vec<usize>), u64>,
+    ) -> u64 {
+        let key = (line.to_vec(), contiguous_counts.to_vec());
+        if let Some(&cached) = cache.get(&key) {
+            return cached;
+        }
+        if contiguous_counts.is_empty() {
+            return (!line.contains(&Item::Broken)) as u64;
+        }
+        let group_size = contiguous_counts[0];
+        if group_size > line.len() {
+            return 0;
+        }
+        let mut num_arrangements = 0;
+        for start in 0..=line.len() - group_size {
+            let end = start + group_size - 1;
+            if satisfiable(line, start, end) {
+                num_arrangements +=
+                    backtrack_arrangements(&line[end + 1..], &contiguous_counts[1..], cache);
+            }
+        }
+        cache.insert(key, num_arrangements);
+        num_arrangements
+    }
+
+    pub fn total(records: &[Record]) -> u64 {
+        let mut cache = HashMap::new();
+        records
+            .iter()
+            .map(|record| {
+                let mut line = record.items.clone();
+                line.insert(0, Item::Functional);
+                backtrack_arrangements(&line, &record.groups, &mut cache)
+            })
+            .sum()
+    }
+}
+
+fn criterion_benchmark_arrangements(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day_12::arrangements");
+    group.sample_size(10);
+    for len in [20, 100] {
+        let records = generate_records(1_000, len);
+        group.bench_function(format!("backtrack/{len}"), |b| {
+            b.iter(|| backtrack::total(&records))
+        });
+        group.bench_function(format!("table/{len}"), |b| {
+            b.iter(|| {
+                records
+                    .iter()
+                    .map(|record| record.arrangements().unwrap())
+                    .sum::<u64>()
+            })
+        });
+    }
 
     group.finish();
 }
@@ -28,6 +143,7 @@ fn criterion_benchmark_part2(c: &mut Criterion) {
 criterion_group!(
     benches,
     criterion_benchmark_part1,
-    criterion_benchmark_part2
+    criterion_benchmark_part2,
+    criterion_benchmark_arrangements
 );
 criterion_main!(benches);
@@ -1,5 +1,7 @@
 use day_12::part1::process;
+use day_12::springs::{parse_records, Printables};
 use miette::Context;
+use miette::IntoDiagnostic;
 
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
@@ -14,6 +16,16 @@ fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
     let file = include_str!("../../input.txt");
+    // `--show N` lists up to N arrangements of every record
+    if let Some(cap) = std::env::args().skip_while(|arg| arg != "--show").nth(1) {
+        let cap = cap.parse().into_diagnostic()?;
+        for record in parse_records(file)? {
+            println!("{} ({})", Printables(&record.items), record.arrangements()?);
+            for items in record.arrangement_list(cap)? {
+                println!("  {}", Printables(&items));
+            }
+        }
+    }
     let result = process(file).context("process part 1")?;
     println!("{}", result);
     Ok(())
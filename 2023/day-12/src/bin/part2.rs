@@ -1,10 +1,16 @@
-use day_12::part2::process;
+use day_12::part2::process_with;
+use day_12::springs::Item;
 use miette::Context;
+use miette::IntoDiagnostic;
 
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
 static ALLOC: dhat::Alloc = dhat::Alloc;
 
+fn arg(name: &str) -> Option<String> {
+    std::env::args().skip_while(|arg| arg != name).nth(1)
+}
+
 #[tracing::instrument]
 fn main() -> miette::Result<()> {
     #[cfg(feature = "dhat-heap")]
@@ -14,7 +20,16 @@ fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
     let file = include_str!("../../input.txt");
-    let result = process(file).context("process part 2")?;
+    // `--unfold N` and `--joiner C` control how the records are unfolded
+    let factor = match arg("--unfold") {
+        Some(factor) => factor.parse().into_diagnostic()?,
+        None => 5,
+    };
+    let joiner = match arg("--joiner") {
+        Some(joiner) => joiner.parse::<char>().into_diagnostic()?.try_into()?,
+        None => Item::Unknown,
+    };
+    let result = process_with(file, factor, joiner).context("process part 2")?;
     println!("{}", result);
     Ok(())
 }
@@ -6,4 +6,20 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("failed to parse condition records: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("unknown spring condition {0:?}")]
+    #[diagnostic(code(aoc::invalid_item))]
+    InvalidItem(char),
+
+    #[error("unfold factor must be at least 1, got {0}")]
+    #[diagnostic(code(aoc::invalid_unfold_factor))]
+    InvalidUnfoldFactor(usize),
+
+    #[error("number of arrangements overflows u64")]
+    #[diagnostic(code(aoc::overflow))]
+    Overflow,
 }
@@ -1,4 +1,13 @@
 pub mod custom_error;
+pub mod springs;
 
 pub mod part1;
 pub mod part2;
+
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = "???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1";
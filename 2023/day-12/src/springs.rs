@@ -0,0 +1,344 @@
+use std::fmt::Display;
+
+use nom::{
+    branch::alt,
+    bytes::complete,
+    character::complete::{line_ending, u64},
+    combinator::{all_consuming, opt},
+    multi::{many1, separated_list1},
+    sequence::{separated_pair, terminated},
+    IResult, Parser,
+};
+
+use crate::custom_error::AocError;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Item {
+    Broken,
+    Functional,
+    Unknown,
+}
+
+impl Display for Item {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Item::Broken => write!(f, "#"),
+            Item::Functional => write!(f, "."),
+            Item::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+impl TryFrom<char> for Item {
+    type Error = AocError;
+
+    fn try_from(c: char) -> Result<Item, AocError> {
+        match c {
+            '#' => Ok(Item::Broken),
+            '.' => Ok(Item::Functional),
+            '?' => Ok(Item::Unknown),
+            _ => Err(AocError::InvalidItem(c)),
+        }
+    }
+}
+
+pub struct Printables<'a>(pub &'a [Item]);
+
+impl Display for Printables<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for item in self.0.iter() {
+            write!(f, "{}", item)?;
+        }
+        Ok(())
+    }
+}
+
+/// One line of the condition records: the springs and the sizes of the
+/// contiguous groups of broken ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub items: Vec<Item>,
+    pub groups: Vec<usize>,
+}
+
+fn parse_record(input: &str) -> IResult<&str, Record> {
+    separated_pair(
+        many1(alt((
+            complete::tag(".").map(|_| Item::Functional),
+            complete::tag("#").map(|_| Item::Broken),
+            complete::tag("?").map(|_| Item::Unknown),
+        ))),
+        complete::tag(" "),
+        separated_list1(complete::tag(","), u64.map(|group| group as usize)),
+    )
+    .map(|(items, groups)| Record { items, groups })
+    .parse(input)
+}
+
+pub fn parse_records(input: &str) -> Result<Vec<Record>, AocError> {
+    all_consuming(terminated(
+        separated_list1(line_ending, parse_record),
+        opt(line_ending),
+    ))(input)
+    .map(|(_, records)| records)
+    .map_err(|e| AocError::ParseError(e.to_string()))
+}
+
+/// Suffix counts for a record: `ways[i][j]` arrangements of `items[i..]`
+/// into `groups[j..]`.
+struct Table<'r> {
+    record: &'r Record,
+    width: usize,
+    next_dot: Vec<usize>,
+    ways: Vec<u64>,
+}
+
+impl<'r> Table<'r> {
+    /// Filled from the end of the row in O(len × groups). Fails if a count
+    /// does not fit in a u64, which heavily unfolded records can reach.
+    fn new(record: &'r Record) -> Result<Table<'r>, AocError> {
+        let (items, groups) = (&record.items, &record.groups);
+        let (n, m) = (items.len(), groups.len());
+
+        // next_dot[i]: first functional spring at or after i
+        let mut next_dot = vec![n; n + 1];
+        for i in (0..n).rev() {
+            next_dot[i] = if items[i] == Item::Functional {
+                i
+            } else {
+                next_dot[i + 1]
+            };
+        }
+        // broken_before[i]: broken springs in items[..i]
+        let mut broken_before = vec![0; n + 1];
+        for i in 0..n {
+            broken_before[i + 1] = broken_before[i] + (items[i] == Item::Broken) as usize;
+        }
+
+        let width = m + 1;
+        let mut table = Table {
+            record,
+            width,
+            next_dot,
+            ways: vec![0u64; (n + 1) * width],
+        };
+        for i in (0..=n).rev() {
+            // No groups left: only if nothing after i has to be broken
+            table.ways[i * width + m] = (broken_before[n] == broken_before[i]) as u64;
+            if i == n {
+                continue;
+            }
+            for j in (0..m).rev() {
+                let mut count: u64 = 0;
+                if items[i] != Item::Broken {
+                    count = table.ways(i + 1, j);
+                }
+                if let Some(next) = table.group_at(i, j) {
+                    count = count
+                        .checked_add(table.ways(next, j + 1))
+                        .ok_or(AocError::Overflow)?;
+                }
+                table.ways[i * width + j] = count;
+            }
+        }
+        Ok(table)
+    }
+
+    fn ways(&self, i: usize, j: usize) -> u64 {
+        self.ways[i * self.width + j]
+    }
+
+    /// Where to continue after placing group `j` at `i`, if there is such a
+    /// group and it fits: it must avoid functional springs and be followed by
+    /// the end of the row or a possible gap.
+    fn group_at(&self, i: usize, j: usize) -> Option<usize> {
+        let items = &self.record.items;
+        let n = items.len();
+        let end = i + self.record.groups.get(j)?;
+        (items[i] != Item::Functional
+            && end <= n
+            && self.next_dot[i] >= end
+            && (end == n || items[end] != Item::Broken))
+            .then_some((end + 1).min(n))
+    }
+}
+
+/// Lazily walks the table, only following branches that lead to at least
+/// one arrangement, so every step makes progress towards the next one.
+pub struct Arrangements<'r> {
+    table: Table<'r>,
+    /// Partial arrangements covering `items[..i]` with `groups[..j]` placed.
+    stack: Vec<(usize, usize, Vec<Item>)>,
+}
+
+impl Iterator for Arrangements<'_> {
+    type Item = Vec<Item>;
+
+    fn next(&mut self) -> Option<Vec<Item>> {
+        let record = self.table.record;
+        let n = record.items.len();
+        while let Some((i, j, mut items)) = self.stack.pop() {
+            if i == n {
+                return Some(items);
+            }
+            if let Some(next) = self.table.group_at(i, j) {
+                if self.table.ways(next, j + 1) > 0 {
+                    let mut placed = items.clone();
+                    let end = i + record.groups[j];
+                    placed.extend(std::iter::repeat_n(Item::Broken, end - i));
+                    if end < n {
+                        placed.push(Item::Functional);
+                    }
+                    self.stack.push((next, j + 1, placed));
+                }
+            }
+            if record.items[i] != Item::Broken && self.table.ways(i + 1, j) > 0 {
+                items.push(Item::Functional);
+                self.stack.push((i + 1, j, items));
+            }
+        }
+        None
+    }
+}
+
+impl Record {
+    /// Counts the ways to fill in the unknown springs.
+    pub fn arrangements(&self) -> Result<u64, AocError> {
+        Ok(Table::new(self)?.ways(0, 0))
+    }
+
+    /// The arrangements themselves, at most `cap` of them, each with every
+    /// unknown spring filled in. Ordered with functional springs before
+    /// broken ones at the first difference.
+    pub fn arrangement_list(
+        &self,
+        cap: usize,
+    ) -> Result<std::iter::Take<Arrangements<'_>>, AocError> {
+        let table = Table::new(self)?;
+        let stack = if table.ways(0, 0) > 0 {
+            vec![(0, 0, Vec::with_capacity(self.items.len()))]
+        } else {
+            vec![]
+        };
+        Ok(Arrangements { table, stack }.take(cap))
+    }
+
+    /// Repeats the record `factor` times, with `joiner` between the copies of
+    /// the springs.
+    pub fn unfold(&self, factor: usize, joiner: Item) -> Result<Record, AocError> {
+        if factor == 0 {
+            return Err(AocError::InvalidUnfoldFactor(factor));
+        }
+        let mut items = Vec::with_capacity((self.items.len() + 1) * factor);
+        for copy in 0..factor {
+            if copy > 0 {
+                items.push(joiner);
+            }
+            items.extend_from_slice(&self.items);
+        }
+        Ok(Record {
+            items,
+            groups: self.groups.repeat(factor),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("???.### 1,1,3", 1)]
+    #[case(".??..??...?##. 1,1,3", 4)]
+    #[case("?#?#?#?#?#?#?#? 1,3,1,6", 1)]
+    #[case("????.#...#... 4,1,1", 1)]
+    #[case("????.######..#####. 1,6,5", 4)]
+    #[case("?###???????? 3,2,1", 10)]
+    #[case("#.# 1", 0)]
+    #[case("??? 4", 0)]
+    fn test_arrangements(#[case] input: &str, #[case] expected: u64) -> miette::Result<()> {
+        assert_eq!(expected, parse_records(input)?[0].arrangements()?);
+        Ok(())
+    }
+
+    /// Tries every way of filling in the unknowns.
+    fn brute_force(record: &Record) -> u64 {
+        let unknowns: Vec<usize> = (0..record.items.len())
+            .filter(|idx| record.items[*idx] == Item::Unknown)
+            .collect();
+        (0..1u32 << unknowns.len())
+            .filter(|mask| {
+                let mut items = record.items.clone();
+                for (bit, idx) in unknowns.iter().enumerate() {
+                    items[*idx] = if mask & (1 << bit) != 0 {
+                        Item::Broken
+                    } else {
+                        Item::Functional
+                    };
+                }
+                let groups: Vec<usize> = items
+                    .split(|item| *item == Item::Functional)
+                    .map(|run| run.len())
+                    .filter(|len| *len > 0)
+                    .collect();
+                groups == record.groups
+            })
+            .count() as u64
+    }
+
+    #[test]
+    fn test_unfold() -> miette::Result<()> {
+        let record = &parse_records(".# 1")?[0];
+        let unfolded = record.unfold(3, Item::Unknown)?;
+        assert_eq!(".#?.#?.#", Printables(&unfolded.items).to_string());
+        assert_eq!(vec![1, 1, 1], unfolded.groups);
+        let joined = record.unfold(2, Item::Functional)?;
+        assert_eq!(".#..#", Printables(&joined.items).to_string());
+        assert!(matches!(
+            record.unfold(0, Item::Unknown),
+            Err(AocError::InvalidUnfoldFactor(0))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_arrangement_list() -> miette::Result<()> {
+        let record = &parse_records(".??..??...?##. 1,1,3")?[0];
+        let rendered: Vec<String> = record
+            .arrangement_list(usize::MAX)?
+            .map(|items| Printables(&items).to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "..#...#...###.",
+                "..#..#....###.",
+                ".#....#...###.",
+                ".#...#....###.",
+            ],
+            rendered
+        );
+        assert_eq!(2, record.arrangement_list(2)?.count());
+        assert_eq!(0, parse_records("??? 4")?[0].arrangement_list(5)?.count());
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches_brute_force() -> miette::Result<()> {
+        let records = parse_records(
+            "?#??.??#?? 2,1,1
+??????????? 1,1,2
+#??#?.?#.?? 4,1,1
+?.?.?.?.?.? 1,1,1",
+        )?;
+        for record in records {
+            assert_eq!(brute_force(&record), record.arrangements()?);
+            assert_eq!(
+                record.arrangements()? as usize,
+                record.arrangement_list(usize::MAX)?.count()
+            );
+        }
+        Ok(())
+    }
+}
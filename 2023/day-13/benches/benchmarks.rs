@@ -1,33 +1,155 @@
-use criterion::{
-    criterion_group, criterion_main, Criterion,
-};
+use aoc_utils::rng::Lcg;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day_13::mirror::parse_patterns;
 use day_13::*;
+use ndarray::Array2;
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
-    let input = include_str!("../input1.txt");
+    let input = include_str!("../input.txt");
 
     let mut group = c.benchmark_group("day_13::part1");
-    group.bench_with_input("part1", input, |b, input| {
-        b.iter(|| part1::process(input))
-    });
+    group.bench_with_input("part1", input, |b, input| b.iter(|| part1::process(input)));
 
     group.finish();
 }
 
 fn criterion_benchmark_part2(c: &mut Criterion) {
-    let input = include_str!("../input2.txt");
+    let input = include_str!("../input.txt");
 
     let mut group = c.benchmark_group("day_13::part2");
-    group.bench_with_input("part2", input, |b, input| {
-        b.iter(|| part2::process(input))
-    });
+    group.bench_with_input("part2", input, |b, input| b.iter(|| part2::process(input)));
 
     group.finish();
 }
 
+/// The previous part 2: flip every cell of a copy of the pattern and rescan
+/// all axes, skipping the one found without a smudge.
+mod clone_and_flip {
+    use ndarray::Array2;
+
+    fn axes(matrix: &Array2<bool>, vertical: bool) -> Vec<usize> {
+        let len = matrix.shape()[vertical as usize];
+        let line = |idx| {
+            if vertical {
+                matrix.column(idx)
+            } else {
+                matrix.row(idx)
+            }
+        };
+        (0..len - 1)
+            .filter(|i| {
+                (0..=*i)
+                    .take_while(|j| i + j + 1 < len)
+                    .all(|j| line(i - j) == line(i + j + 1))
+            })
+            .collect()
+    }
+
+    pub fn summarize(matrices: &[Array2<bool>]) -> u64 {
+        let mut sum = 0;
+        'outer: for matrix in matrices {
+            let old_vertical = axes(matrix, true).first().copied();
+            let old_horizontal = axes(matrix, false).first().copied();
+            for pos in (0..matrix.shape()[0])
+                .flat_map(|row| (0..matrix.shape()[1]).map(move |col| (row, col)))
+            {
+                let mut flipped = matrix.clone();
+                flipped[pos] = !flipped[pos];
+                if let Some(axis) = axes(&flipped, true)
+                    .into_iter()
+                    .find(|axis| old_vertical != Some(*axis))
+                {
+                    sum += axis as u64 + 1;
+                    continue 'outer;
+                }
+                if let Some(axis) = axes(&flipped, false)
+                    .into_iter()
+                    .find(|axis| old_vertical.is_some() || old_horizontal != Some(*axis))
+                {
+                    sum += 100 * (axis as u64 + 1);
+                    continue 'outer;
+                }
+            }
+        }
+        sum
+    }
+}
+
+/// Deterministic pseudo-random `size`×`size` patterns with a horizontal
+/// mirror line in the middle and one smudge, so runs are comparable.
+fn generate_patterns(count: usize, size: usize) -> String {
+    let mut rng = Lcg::default();
+    let mut patterns = vec![];
+    for _ in 0..count {
+        let half: Vec<Vec<u8>> = (0..size / 2)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if rng.below(2) == 0 { b'#' } else { b'.' })
+                    .collect()
+            })
+            .collect();
+        let mut rows: Vec<Vec<u8>> = half.iter().rev().chain(half.iter()).cloned().collect();
+        let (row, col) = (rng.below(size), rng.below(size));
+        rows[row][col] = if rows[row][col] == b'#' { b'.' } else { b'#' };
+        patterns.push(
+            rows.into_iter()
+                .map(|row| String::from_utf8(row).unwrap())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+    }
+    patterns.join("\n\n")
+}
+
+fn criterion_benchmark_smudge(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day_13::smudge");
+    group.sample_size(10);
+    for size in [16, 48] {
+        let input = generate_patterns(20, size);
+        let matrices: Vec<Array2<bool>> = input
+            .split("\n\n")
+            .map(|pattern| {
+                let rows: Vec<&str> = pattern.lines().collect();
+                Array2::from_shape_fn((rows.len(), rows[0].len()), |(row, col)| {
+                    rows[row].as_bytes()[col] == b'#'
+                })
+            })
+            .collect();
+        let patterns = parse_patterns(&input).unwrap();
+        assert_eq!(
+            clone_and_flip::summarize(&matrices),
+            patterns
+                .iter()
+                .filter_map(|pattern| pattern.reflection(1))
+                .map(|reflection| reflection.summary())
+                .sum::<u64>()
+        );
+        group.bench_with_input(
+            BenchmarkId::new("clone_and_flip", size),
+            &matrices,
+            |b, matrices| b.iter(|| clone_and_flip::summarize(matrices)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("mismatch_count", size),
+            &patterns,
+            |b, patterns| {
+                b.iter(|| {
+                    patterns
+                        .iter()
+                        .filter_map(|pattern| pattern.reflection(1))
+                        .map(|reflection| reflection.summary())
+                        .sum::<u64>()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
 criterion_group!(
     benches,
     criterion_benchmark_part1,
-    criterion_benchmark_part2
+    criterion_benchmark_part2,
+    criterion_benchmark_smudge
 );
 criterion_main!(benches);
@@ -1,3 +1,4 @@
+use day_13::mirror::reports;
 use day_13::part1::process;
 use miette::Context;
 
@@ -14,6 +15,12 @@ fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
     let file = include_str!("../../input.txt");
+    // `--report` shows where each pattern reflects
+    if std::env::args().any(|arg| arg == "--report") {
+        for report in reports(file, 0)? {
+            println!("{}\n{report}", report.summary_line());
+        }
+    }
     let result = process(file).context("process part 1")?;
     println!("{}", result);
     Ok(())
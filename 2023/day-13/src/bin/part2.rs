@@ -1,5 +1,6 @@
-use day_13::part2::process;
+use day_13::mirror::{reports, summarize};
 use miette::Context;
+use miette::IntoDiagnostic;
 
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
@@ -14,7 +15,18 @@ fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
     let file = include_str!("../../input.txt");
-    let result = process(file).context("process part 2")?;
+    // `--smudges K` looks for axes with exactly K mismatched cells instead
+    let smudges = match std::env::args().skip_while(|arg| arg != "--smudges").nth(1) {
+        Some(smudges) => smudges.parse().into_diagnostic()?,
+        None => 1,
+    };
+    // `--report` shows where each pattern reflects
+    if std::env::args().any(|arg| arg == "--report") {
+        for report in reports(file, smudges)? {
+            println!("{}\n{report}", report.summary_line());
+        }
+    }
+    let result = summarize(file, smudges).context("process part 2")?;
     println!("{}", result);
     Ok(())
 }
@@ -6,4 +6,12 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("failed to parse patterns: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("pattern {pattern} has no reflection axis with exactly {smudges} smudges")]
+    #[diagnostic(code(aoc::no_reflection))]
+    NoReflection { pattern: usize, smudges: u32 },
 }
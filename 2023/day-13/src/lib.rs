@@ -1,4 +1,22 @@
 pub mod custom_error;
+pub mod mirror;
 
 pub mod part1;
 pub mod part2;
+
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
@@ -0,0 +1,493 @@
+use std::fmt;
+
+use nom::{
+    character::complete::{line_ending, multispace0, one_of},
+    combinator::all_consuming,
+    multi::{many1, separated_list1},
+    sequence::{pair, terminated},
+    IResult, Parser,
+};
+
+use crate::custom_error::AocError;
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Vec<Vec<bool>>>> {
+    all_consuming(terminated(
+        separated_list1(
+            pair(line_ending, line_ending),
+            separated_list1(line_ending, many1(one_of(".#").map(|c| c == '#'))),
+        ),
+        multispace0,
+    ))(input)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// The axis runs between two columns.
+    Vertical,
+    /// The axis runs between two rows.
+    Horizontal,
+}
+
+/// A mirror line and the cells that had to differ for it to hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reflection {
+    pub orientation: Orientation,
+    /// Number of columns left of, or rows above, the axis.
+    pub axis: usize,
+    /// Number of columns or rows on each side that mirror each other; the
+    /// rest of the longer side has no image.
+    pub extent: usize,
+    /// `(row, col)` of every mismatched cell. Either cell of a mismatched
+    /// pair could be the smudge; this is the one before the axis.
+    pub smudges: Vec<(usize, usize)>,
+}
+
+impl Reflection {
+    /// The puzzle's summary: columns left of a vertical axis, or 100 times
+    /// the rows above a horizontal one.
+    pub fn summary(&self) -> u64 {
+        match self.orientation {
+            Orientation::Vertical => self.axis as u64,
+            Orientation::Horizontal => 100 * self.axis as u64,
+        }
+    }
+}
+
+/// A line of cells as a bitmask split into 64-bit words; bit `idx % 64` of
+/// word `idx / 64` is set for a rock.
+type Line = Vec<u64>;
+
+fn new_line(len: usize) -> Line {
+    vec![0; len.div_ceil(64)]
+}
+
+fn set_bit(line: &mut Line, idx: usize) {
+    line[idx / 64] |= 1 << (idx % 64);
+}
+
+/// A pattern of ash and rocks, stored both as row bitmasks and as column
+/// bitmasks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    rows: Vec<Line>,
+    cols: Vec<Line>,
+}
+
+impl Pattern {
+    fn from_cells(index: usize, cells: &[Vec<bool>]) -> Result<Pattern, AocError> {
+        let width = cells[0].len();
+        if cells.iter().any(|row| row.len() != width) {
+            return Err(AocError::ParseError(format!(
+                "rows of pattern {} differ in length",
+                index + 1
+            )));
+        }
+        let mut rows = vec![new_line(width); cells.len()];
+        let mut cols = vec![new_line(cells.len()); width];
+        for (row, line) in cells.iter().enumerate() {
+            for (col, rock) in line.iter().enumerate() {
+                if *rock {
+                    set_bit(&mut rows[row], col);
+                    set_bit(&mut cols[col], row);
+                }
+            }
+        }
+        Ok(Pattern { rows, cols })
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.cols.len()
+    }
+
+    pub fn is_rock(&self, (row, col): (usize, usize)) -> bool {
+        self.rows[row][col / 64] & (1 << (col % 64)) != 0
+    }
+
+    /// Every axis, vertical ones first, across which exactly `smudges` cells
+    /// do not match their mirror image.
+    pub fn reflections(&self, smudges: u32) -> Vec<Reflection> {
+        let vertical = mirror_axes(&self.cols, smudges).map(|(axis, mismatches)| Reflection {
+            orientation: Orientation::Vertical,
+            axis,
+            extent: axis.min(self.width() - axis),
+            smudges: mismatches.map(|(col, row)| (row, col)).collect(),
+        });
+        let horizontal = mirror_axes(&self.rows, smudges).map(|(axis, mismatches)| Reflection {
+            orientation: Orientation::Horizontal,
+            axis,
+            extent: axis.min(self.height() - axis),
+            smudges: mismatches.collect(),
+        });
+        vertical.chain(horizontal).collect()
+    }
+
+    /// The first axis with exactly `smudges` mismatched cells.
+    pub fn reflection(&self, smudges: u32) -> Option<Reflection> {
+        self.reflections(smudges).into_iter().next()
+    }
+}
+
+/// Axes between `lines` with exactly `smudges` mismatched bits between each
+/// line and its mirror image, with the mismatches as `(line, index)` on the
+/// side before the axis. Each candidate stops as soon as it has too many.
+fn mirror_axes(
+    lines: &[Line],
+    smudges: u32,
+) -> impl Iterator<Item = (usize, impl Iterator<Item = (usize, usize)>)> + '_ {
+    (1..lines.len()).filter_map(move |axis| {
+        let mut mismatches = 0;
+        let mut diffs = vec![];
+        for (before, after) in (0..axis).rev().zip(axis..lines.len()) {
+            for (word, (a, b)) in lines[before].iter().zip(&lines[after]).enumerate() {
+                let diff = a ^ b;
+                mismatches += diff.count_ones();
+                if mismatches > smudges {
+                    return None;
+                }
+                if diff != 0 {
+                    diffs.push((before, word, diff));
+                }
+            }
+        }
+        (mismatches == smudges).then(|| {
+            let positions = diffs.into_iter().flat_map(|(line, word, diff)| {
+                (0..64)
+                    .filter(move |bit| diff & (1 << bit) != 0)
+                    .map(move |bit| (line, word * 64 + bit))
+            });
+            (axis, positions)
+        })
+    })
+}
+
+pub fn parse_patterns(input: &str) -> Result<Vec<Pattern>, AocError> {
+    let (_, patterns) = parse_input(input).map_err(|e| AocError::ParseError(e.to_string()))?;
+    patterns
+        .iter()
+        .enumerate()
+        .map(|(index, cells)| Pattern::from_cells(index, cells))
+        .collect()
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.height() {
+            for col in 0..self.width() {
+                write!(f, "{}", if self.is_rock((row, col)) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// The reflection found in one pattern of the notes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// Position of the pattern in the notes, counting from 1.
+    pub index: usize,
+    pub pattern: Pattern,
+    pub reflection: Reflection,
+    /// What the pattern adds to the summary.
+    pub contribution: u64,
+}
+
+impl Report {
+    /// One line describing the axis, e.g.
+    /// `pattern 2: Horizontal axis at 4, 3 mirrored, smudges [], adds 400`.
+    pub fn summary_line(&self) -> String {
+        let reflection = &self.reflection;
+        format!(
+            "pattern {}: {:?} axis at {}, {} mirrored, smudges {:?}, adds {}",
+            self.index,
+            reflection.orientation,
+            reflection.axis,
+            reflection.extent,
+            reflection.smudges,
+            self.contribution
+        )
+    }
+}
+
+/// Last digit of a 1-based line number, as the puzzle labels lines.
+fn label(idx: usize) -> char {
+    char::from_digit(((idx + 1) % 10) as u32, 10).unwrap()
+}
+
+/// The pattern with the axis marked as in the puzzle text: `><` above and
+/// below a vertical axis, `v^` beside a horizontal one, with line numbers.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pattern = self.pattern.to_string();
+        let axis = self.reflection.axis;
+        match self.reflection.orientation {
+            Orientation::Vertical => {
+                let labels: String = (0..self.pattern.width()).map(label).collect();
+                let markers = format!("{}><", " ".repeat(axis - 1));
+                writeln!(f, "{labels}\n{markers}")?;
+                write!(f, "{pattern}")?;
+                writeln!(f, "{markers}\n{labels}")
+            }
+            Orientation::Horizontal => {
+                for (row, line) in pattern.lines().enumerate() {
+                    let marker = match row + 1 {
+                        r if r == axis => 'v',
+                        r if r == axis + 1 => '^',
+                        _ => ' ',
+                    };
+                    let label = label(row);
+                    writeln!(f, "{label}{marker}{line}{marker}{label}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The first axis with exactly `smudges` mismatches in each pattern, failing
+/// on the first pattern without one.
+pub fn reports(input: &str, smudges: u32) -> Result<Vec<Report>, AocError> {
+    parse_patterns(input)?
+        .into_iter()
+        .enumerate()
+        .map(|(index, pattern)| {
+            let reflection = pattern.reflection(smudges).ok_or(AocError::NoReflection {
+                pattern: index + 1,
+                smudges,
+            })?;
+            Ok(Report {
+                index: index + 1,
+                contribution: reflection.summary(),
+                pattern,
+                reflection,
+            })
+        })
+        .collect()
+}
+
+/// Sums the summaries of the first axis with exactly `smudges` mismatches
+/// in each pattern.
+pub fn summarize(input: &str, smudges: u32) -> Result<u64, AocError> {
+    Ok(reports(input, smudges)?
+        .iter()
+        .map(|report| report.contribution)
+        .sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::EXAMPLE;
+
+    fn reflection(
+        orientation: Orientation,
+        axis: usize,
+        extent: usize,
+        smudges: &[(usize, usize)],
+    ) -> Reflection {
+        Reflection {
+            orientation,
+            axis,
+            extent,
+            smudges: smudges.to_vec(),
+        }
+    }
+
+    #[rstest]
+    #[case(0, vec![
+        reflection(Orientation::Vertical, 5, 4, &[]),
+        reflection(Orientation::Horizontal, 4, 3, &[]),
+    ])]
+    // The puzzle fixes the second smudge at (1, 4), its mirror image
+    #[case(1, vec![
+        reflection(Orientation::Horizontal, 3, 3, &[(0, 0)]),
+        reflection(Orientation::Horizontal, 1, 1, &[(0, 4)]),
+    ])]
+    fn test_reflection(
+        #[case] smudges: u32,
+        #[case] expected: Vec<Reflection>,
+    ) -> miette::Result<()> {
+        let reflections: Vec<Option<Reflection>> = parse_patterns(EXAMPLE)?
+            .iter()
+            .map(|pattern| pattern.reflection(smudges))
+            .collect();
+        assert_eq!(
+            expected.into_iter().map(Some).collect::<Vec<_>>(),
+            reflections
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(
+        "..####..##..##..#
+...#..##.####.##.
+.##.#.##..##..##.
+...#..##.####.##.
+.##..#..#....#..#
+##.##.##########.
+#########.##.####",
+        Some(11)
+    )]
+    fn test_vertical_axis(
+        #[case] input: &str,
+        #[case] expected: Option<usize>,
+    ) -> miette::Result<()> {
+        let pattern = &parse_patterns(input)?[0];
+        assert_eq!(
+            expected,
+            pattern
+                .reflection(0)
+                .filter(|r| r.orientation == Orientation::Vertical)
+                .map(|r| r.axis)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_reflections() -> miette::Result<()> {
+        let pattern = &parse_patterns(
+            "###.##.##
+##.####.#
+##.#..#.#
+####..###
+....##...
+##.#..#.#
+...#..#..
+##.####.#
+##......#
+##......#
+..#.##.#.
+...#..#..
+##.####.#
+....##...
+...####..
+....##...
+##.####.#",
+        )?[0];
+        assert_eq!(
+            vec![1, 5],
+            pattern
+                .reflections(0)
+                .iter()
+                .map(|r| r.axis)
+                .collect::<Vec<_>>()
+        );
+        assert!(pattern.reflection(2).is_none());
+        // Columns 1 and 2 of the first example differ in two cells
+        assert_eq!(
+            vec![reflection(Orientation::Vertical, 1, 1, &[(0, 0), (6, 0)])],
+            parse_patterns(EXAMPLE)?[0].reflections(2)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_reports() -> miette::Result<()> {
+        let reports = reports(EXAMPLE, 0)?;
+        assert_eq!(
+            vec![5, 400],
+            reports.iter().map(|r| r.contribution).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "pattern 2: Horizontal axis at 4, 3 mirrored, smudges [], adds 400",
+            reports[1].summary_line()
+        );
+        assert_eq!(
+            "123456789
+    ><
+#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+    ><
+123456789
+",
+            reports[0].to_string()
+        );
+        assert_eq!(
+            "1 #...##..# 1
+2 #....#..# 2
+3 ..##..### 3
+4v#####.##.v4
+5^#####.##.^5
+6 ..##..### 6
+7 #....#..# 7
+",
+            reports[1].to_string()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_reflection() -> miette::Result<()> {
+        let input = format!(
+            "{EXAMPLE}
+
+#.##..##.
+..#.##.#.
+##..#...#
+##...#..#
+..#.##.#.
+..##..##.
+#.#.##.#."
+        );
+        for smudges in [0, 1] {
+            assert!(matches!(
+                reports(&input, smudges),
+                Err(AocError::NoReflection { pattern: 3, .. })
+            ));
+        }
+        // Both cells next to the vertical axis differ
+        assert!(matches!(
+            reports("#.\n.#", 1),
+            Err(AocError::NoReflection {
+                pattern: 1,
+                smudges: 1
+            })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_wide_pattern() -> miette::Result<()> {
+        // 130 columns mirrored after column 125, so lines span two words
+        let left: String = (0..125)
+            .map(|col| if col % 3 == 0 { '#' } else { '.' })
+            .collect();
+        let row = format!("{left}{}", left.chars().rev().take(5).collect::<String>());
+        let mut smudged = row.clone();
+        smudged.replace_range(127..128, if &row[127..128] == "#" { "." } else { "#" });
+
+        let pattern = &parse_patterns(&row)?[0];
+        assert_eq!(130, pattern.width());
+        assert!(pattern
+            .reflections(0)
+            .contains(&reflection(Orientation::Vertical, 125, 5, &[])));
+        let pattern = &parse_patterns(&format!("{row}\n{smudged}"))?[0];
+        assert!(pattern.reflections(1).contains(&reflection(
+            Orientation::Vertical,
+            125,
+            5,
+            &[(1, 122)]
+        )));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        assert!(matches!(
+            parse_patterns("#.\n#"),
+            Err(AocError::ParseError(_))
+        ));
+    }
+}
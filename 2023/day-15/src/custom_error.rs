@@ -6,4 +6,12 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("initialization sequence is not ASCII")]
+    #[diagnostic(code(aoc::non_ascii))]
+    NonAscii,
+
+    #[error("invalid step `{0}`")]
+    #[diagnostic(code(aoc::invalid_step))]
+    InvalidStep(String),
 }
@@ -0,0 +1,18 @@
+/// The puzzle's HASH algorithm over ASCII bytes.
+pub fn hash(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .fold(0, |acc, byte| (acc + *byte as usize) * 17 % 256)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(52, hash(b"HASH"));
+        assert_eq!(0, hash(b"rn"));
+        assert_eq!(3, hash(b"pc"));
+    }
+}
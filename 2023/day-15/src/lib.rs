@@ -1,5 +1,5 @@
-#![feature(ascii_char)]
 pub mod custom_error;
+pub mod hash;
 
 pub mod part1;
 pub mod part2;
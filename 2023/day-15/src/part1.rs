@@ -1,27 +1,12 @@
-use nom::{
-    bytes::complete::tag,
-    character::complete::none_of,
-    multi::{many1, separated_list1},
-    IResult,
-};
+use aoc_utils::ascii::split_ascii;
 
 use crate::custom_error::AocError;
-
-fn parse_input(input: &str) -> IResult<&str, Vec<Vec<char>>> {
-    separated_list1(tag(","), many1(none_of(",\n")))(input)
-}
+use crate::hash::hash;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<usize, AocError> {
-    let (_, parsed) = parse_input(input).unwrap();
-    let sum = parsed
-        .into_iter()
-        .map(|word| {
-            word.iter()
-                .fold(0, |acc, c| (acc + (*c as usize)) * 17 % 256)
-        })
-        .sum();
-    Ok(sum)
+    let steps = split_ascii(input, b',').ok_or(AocError::NonAscii)?;
+    Ok(steps.map(hash).sum())
 }
 
 #[cfg(test)]
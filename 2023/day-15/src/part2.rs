@@ -1,58 +1,43 @@
-use std::collections::HashMap;
-
-use nom::{
-    bytes::complete::tag,
-    character::complete::none_of,
-    multi::{many1, separated_list1},
-    IResult,
-};
+use aoc_utils::ascii::split_ascii;
 
 use crate::custom_error::AocError;
-
-fn parse_input(input: &str) -> IResult<&str, Vec<Vec<char>>> {
-    separated_list1(tag(","), many1(none_of(",\n")))(input)
-}
+use crate::hash::hash;
 
 #[derive(Debug)]
-struct Lens {
-    label: String,
+struct Lens<'a> {
+    label: &'a [u8],
     strength: usize,
 }
 
+fn invalid_step(step: &[u8]) -> AocError {
+    AocError::InvalidStep(String::from_utf8_lossy(step).into_owned())
+}
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<usize, AocError> {
-    let (_, parsed) = parse_input(input).unwrap();
-    let mut boxes = HashMap::<usize, Vec<Lens>>::new();
-    for i in 0..256 {
-        boxes.insert(i, vec![]);
-    }
-    for word in parsed.iter() {
-        let box_num = word
+    let mut boxes: Vec<Vec<Lens>> = (0..256).map(|_| Vec::new()).collect();
+    for step in split_ascii(input, b',').ok_or(AocError::NonAscii)? {
+        if let Some(label) = step.strip_suffix(b"-") {
+            boxes[hash(label)].retain(|lens| lens.label != label);
+            continue;
+        }
+        let idx = step
             .iter()
-            .filter(|c| c.is_ascii_alphabetic())
-            .fold(0, |acc, c| (acc + (*c as usize)) * 17 % 256);
-        if word.contains(&'=') {
-            let parts = word.iter().collect::<String>();
-            let parts = parts.split('=').collect::<Vec<&str>>();
-            let label = parts[0].to_owned();
-            let strength = parts[1].parse::<usize>().unwrap();
-            let abox = boxes.get_mut(&box_num).unwrap();
-            if let Some(idx) = abox.iter().position(|i| i.label == label) {
-                abox.remove(idx);
-                abox.insert(idx, Lens { label, strength });
-            } else {
-                abox.push(Lens { label, strength });
-            }
-        } else {
-            let label = word[0..word.len() - 1].iter().collect::<String>();
-            let abox = boxes.get_mut(&box_num).unwrap();
-            if let Some(idx) = abox.iter().position(|i| i.label == label) {
-                abox.remove(idx);
-            }
+            .position(|byte| *byte == b'=')
+            .ok_or_else(|| invalid_step(step))?;
+        let (label, strength) = (&step[..idx], &step[idx + 1..]);
+        let strength = std::str::from_utf8(strength)
+            .ok()
+            .and_then(|strength| strength.parse().ok())
+            .ok_or_else(|| invalid_step(step))?;
+        let abox = &mut boxes[hash(label)];
+        match abox.iter_mut().find(|lens| lens.label == label) {
+            Some(lens) => lens.strength = strength,
+            None => abox.push(Lens { label, strength }),
         }
     }
     let mut focusing_power = 0;
-    for (box_num, lens) in boxes.iter() {
+    for (box_num, lens) in boxes.iter().enumerate() {
         for (slot_num, lens) in lens.iter().enumerate() {
             focusing_power += (box_num + 1) * (slot_num + 1) * lens.strength;
         }
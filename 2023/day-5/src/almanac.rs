@@ -0,0 +1,300 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::custom_error::AocError;
+use crate::range_map::{parse_map_entry, MapEntry, RangeMap};
+
+/// A `X-to-Y map:` section.
+#[derive(Debug, Clone)]
+pub struct Layer {
+    pub destination: String,
+    pub map: RangeMap,
+}
+
+/// The parsed almanac. Layers are keyed by their source category, so the
+/// sections may appear in any order in the input.
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    layers: HashMap<String, Layer>,
+    issues: Vec<AocError>,
+}
+
+/// A section as read from the input, with the line number of each entry.
+struct Section {
+    source: String,
+    destination: String,
+    entries: Vec<(usize, MapEntry)>,
+}
+
+fn parse_header(line_number: usize, line: &str) -> Result<(String, String), AocError> {
+    line.strip_suffix(" map:")
+        .and_then(|name| name.split_once("-to-"))
+        .map(|(source, destination)| (source.to_string(), destination.to_string()))
+        .ok_or_else(|| AocError::InvalidHeader {
+            line: line_number,
+            content: line.to_string(),
+        })
+}
+
+fn parse_seeds((line_number, line): (usize, &str)) -> Result<Vec<u64>, AocError> {
+    let malformed = |reason: String| AocError::MalformedSeeds {
+        line: line_number,
+        content: line.to_string(),
+        reason,
+    };
+
+    let seeds = line
+        .strip_prefix("seeds:")
+        .ok_or_else(|| malformed("expected `seeds:`".to_string()))?;
+    seeds
+        .split_whitespace()
+        .map(|seed| {
+            seed.parse::<u64>()
+                .map_err(|e| malformed(format!("`{}`: {}", seed, e)))
+        })
+        .collect()
+}
+
+/// Reports every pair of entries whose source ranges overlap.
+fn find_overlaps(category: &str, entries: &mut [(usize, MapEntry)]) -> Vec<AocError> {
+    entries.sort_by_key(|(_, entry)| entry.source_range.start);
+    let mut issues = Vec::new();
+    for (idx, (first, entry)) in entries.iter().enumerate() {
+        for (second, other) in entries[idx + 1..].iter() {
+            if other.source_range.start >= entry.source_range.end {
+                break;
+            }
+            if !entry.source_range.is_empty() && !other.source_range.is_empty() {
+                issues.push(AocError::OverlappingEntries {
+                    category: category.to_string(),
+                    first: *first.min(second),
+                    second: *first.max(second),
+                });
+            }
+        }
+    }
+    issues
+}
+
+impl Almanac {
+    /// Parses the almanac, skipping malformed and overflowing entries. The
+    /// skipped entries and any overlaps are available from `issues`.
+    pub fn parse(input: &str) -> Result<Almanac, AocError> {
+        let mut lines = input.lines().enumerate().map(|(idx, line)| (idx + 1, line));
+
+        let seeds = parse_seeds(lines.next().unwrap_or((1, "")))?;
+
+        let mut issues = Vec::new();
+        let mut sections: Vec<Section> = Vec::new();
+        for (line_number, line) in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if line.ends_with(':') {
+                let (source, destination) = parse_header(line_number, line)?;
+                sections.push(Section {
+                    source,
+                    destination,
+                    entries: Vec::new(),
+                });
+                continue;
+            }
+            let Some(section) = sections.last_mut() else {
+                return Err(AocError::InvalidHeader {
+                    line: line_number,
+                    content: line.to_string(),
+                });
+            };
+            match parse_map_entry(line_number, line) {
+                Ok(entry) => section.entries.push((line_number, entry)),
+                Err(issue) => issues.push(issue),
+            }
+        }
+
+        let mut layers = HashMap::new();
+        for Section {
+            source,
+            destination,
+            mut entries,
+        } in sections
+        {
+            issues.extend(find_overlaps(&source, &mut entries));
+            let map = RangeMap::new(entries.into_iter().map(|(_, entry)| entry).collect());
+            if layers
+                .insert(source.clone(), Layer { destination, map })
+                .is_some()
+            {
+                return Err(AocError::DuplicateMap(source));
+            }
+        }
+
+        let almanac = Almanac {
+            seeds,
+            layers,
+            issues,
+        };
+        almanac.validate()?;
+        Ok(almanac)
+    }
+
+    /// Like `parse`, but rejects inputs with malformed, overflowing or
+    /// overlapping entries.
+    pub fn parse_strict(input: &str) -> Result<Almanac, AocError> {
+        let mut almanac = Almanac::parse(input)?;
+        if almanac.issues.is_empty() {
+            Ok(almanac)
+        } else {
+            Err(AocError::InvalidAlmanac {
+                issues: std::mem::take(&mut almanac.issues),
+            })
+        }
+    }
+
+    /// Problems tolerated by the lenient `parse`.
+    pub fn issues(&self) -> &[AocError] {
+        &self.issues
+    }
+
+    /// Checks that the layers form a single chain of categories: exactly one
+    /// category nothing maps into, and every layer reachable from it without
+    /// revisiting a category.
+    fn validate(&self) -> Result<(), AocError> {
+        let destinations: HashSet<&str> = self
+            .layers
+            .values()
+            .map(|layer| layer.destination.as_str())
+            .collect();
+        let starts: Vec<&str> = self
+            .layers
+            .keys()
+            .map(String::as_str)
+            .filter(|source| !destinations.contains(source))
+            .collect();
+        let start = match starts.as_slice() {
+            [start] => *start,
+            [] if self.layers.is_empty() => return Ok(()),
+            [] => {
+                let category = self.layers.keys().min().cloned().unwrap_or_default();
+                return Err(AocError::CategoryCycle(category));
+            }
+            _ => return Err(AocError::DisconnectedMaps),
+        };
+
+        let mut visited = HashSet::from([start]);
+        let mut current = start;
+        while let Some(layer) = self.layers.get(current) {
+            current = layer.destination.as_str();
+            if !visited.insert(current) {
+                return Err(AocError::CategoryCycle(current.to_string()));
+            }
+        }
+        if visited.len() != self.layers.len() + 1 {
+            return Err(AocError::DisconnectedMaps);
+        }
+        Ok(())
+    }
+
+    /// Returns the layers leading from category `from` to category `to`.
+    pub fn path(&self, from: &str, to: &str) -> Result<Vec<&RangeMap>, AocError> {
+        let mut res = Vec::new();
+        let mut current = from;
+        while current != to {
+            let layer = self.layers.get(current).ok_or_else(|| AocError::NoPath {
+                from: from.to_string(),
+                to: to.to_string(),
+            })?;
+            res.push(&layer.map);
+            current = layer.destination.as_str();
+            if res.len() > self.layers.len() {
+                return Err(AocError::CategoryCycle(current.to_string()));
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EXAMPLE;
+
+    #[test]
+    fn test_path_between_categories() -> Result<(), AocError> {
+        let almanac = Almanac::parse(EXAMPLE)?;
+        assert_eq!(7, almanac.path("seed", "location")?.len());
+        assert_eq!(4, almanac.path("soil", "temperature")?.len());
+        assert!(almanac.path("location", "seed").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_reordered_sections() -> Result<(), AocError> {
+        let mut sections: Vec<&str> = EXAMPLE.trim_end().split("\n\n").collect();
+        sections[1..].reverse();
+        let almanac = Almanac::parse(&sections.join("\n\n"))?;
+        assert_eq!(7, almanac.path("seed", "location")?.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_graphs() {
+        let gap = "seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nwater-to-light map:\n1 2 3";
+        assert!(matches!(
+            Almanac::parse(gap),
+            Err(AocError::DisconnectedMaps)
+        ));
+        let cycle = "seeds: 1\n\nseed-to-soil map:\n1 2 3\n\nsoil-to-water map:\n1 2 3\n\nwater-to-soil map:\n1 2 3";
+        assert!(matches!(
+            Almanac::parse(cycle),
+            Err(AocError::CategoryCycle(_))
+        ));
+    }
+
+    #[test]
+    fn test_issues_reported_with_line_numbers() -> Result<(), AocError> {
+        let input = "seeds: 1
+
+seed-to-soil map:
+10 0 5
+1 x 3
+20 3 5
+1 2
+0 18446744073709551615 2";
+        let almanac = Almanac::parse(input)?;
+        let issues = almanac
+            .issues()
+            .iter()
+            .map(|issue| issue.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "line 5: malformed map entry `1 x 3`: `x`: invalid digit found in string",
+                "line 7: malformed map entry `1 2`: expected 3 numbers, found 2",
+                "line 8: map entry `0 18446744073709551615 2` overflows u64",
+                "lines 4 and 6: overlapping source ranges in the `seed` map",
+            ],
+            issues
+        );
+        assert!(matches!(
+            Almanac::parse_strict(input),
+            Err(AocError::InvalidAlmanac { issues }) if issues.len() == 4
+        ));
+        assert!(Almanac::parse_strict(EXAMPLE).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_malformed_seeds() {
+        let errors = ["seeds: 1 x 3\n", "seeds: 1 -3\n", "1 2 3\n", ""]
+            .map(|input| Almanac::parse(input).unwrap_err().to_string());
+        assert_eq!(
+            [
+                "line 1: malformed seed list `seeds: 1 x 3`: `x`: invalid digit found in string",
+                "line 1: malformed seed list `seeds: 1 -3`: `-3`: invalid digit found in string",
+                "line 1: malformed seed list `1 2 3`: expected `seeds:`",
+                "line 1: malformed seed list ``: expected `seeds:`",
+            ],
+            errors
+        );
+    }
+}
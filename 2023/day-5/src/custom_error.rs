@@ -0,0 +1,80 @@
+use miette::Diagnostic;
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    #[error("line {line}: malformed map entry `{content}`: {reason}")]
+    #[diagnostic(
+        code(aoc::malformed_entry),
+        help("entries are three numbers: destination, source and range length")
+    )]
+    MalformedEntry {
+        line: usize,
+        content: String,
+        reason: String,
+    },
+
+    #[error("line {line}: malformed seed list `{content}`: {reason}")]
+    #[diagnostic(
+        code(aoc::malformed_seeds),
+        help("the first line lists the seeds, e.g. `seeds: 79 14 55 13`")
+    )]
+    MalformedSeeds {
+        line: usize,
+        content: String,
+        reason: String,
+    },
+
+    #[error("line {line}: seed {seed} is too large to map")]
+    #[diagnostic(code(aoc::seed_overflow))]
+    SeedOverflow { line: usize, seed: u64 },
+
+    #[error("line {line}: map entry `{content}` overflows u64")]
+    #[diagnostic(code(aoc::entry_overflow))]
+    EntryOverflow { line: usize, content: String },
+
+    #[error("lines {first} and {second}: overlapping source ranges in the `{category}` map")]
+    #[diagnostic(
+        code(aoc::overlapping_entries),
+        help("the entry with the lower source start wins")
+    )]
+    OverlappingEntries {
+        category: String,
+        first: usize,
+        second: usize,
+    },
+
+    #[error("line {line}: invalid map header `{content}`")]
+    #[diagnostic(
+        code(aoc::invalid_header),
+        help("headers look like `seed-to-soil map:`")
+    )]
+    InvalidHeader { line: usize, content: String },
+
+    #[error("multiple maps from `{0}`")]
+    #[diagnostic(code(aoc::duplicate_map))]
+    DuplicateMap(String),
+
+    #[error("almanac maps form a cycle through `{0}`")]
+    #[diagnostic(code(aoc::category_cycle))]
+    CategoryCycle(String),
+
+    #[error("almanac maps do not form a single chain")]
+    #[diagnostic(code(aoc::disconnected_maps))]
+    DisconnectedMaps,
+
+    #[error("no map leads from `{from}` to `{to}`")]
+    #[diagnostic(code(aoc::no_path))]
+    NoPath { from: String, to: String },
+
+    #[error("almanac has {} problem(s)", issues.len())]
+    #[diagnostic(code(aoc::invalid_almanac))]
+    InvalidAlmanac {
+        #[related]
+        issues: Vec<AocError>,
+    },
+}
@@ -1,8 +1,110 @@
+pub mod almanac;
+pub mod custom_error;
 pub mod part1;
 pub mod part2;
+pub mod piecewise;
+pub mod range_map;
+
+#[cfg(test)]
+pub(crate) const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
 
 use std::error::Error;
+use std::fs;
+
+use almanac::Almanac;
+use piecewise::PiecewiseMap;
+
+fn composed_map(from: &str, to: &str) -> Result<PiecewiseMap, Box<dyn Error>> {
+    let content = fs::read_to_string("input.txt")?;
+    let almanac = Almanac::parse(&content)?;
+    Ok(PiecewiseMap::compose(almanac.path(from, to)?))
+}
 
 pub fn main() -> Result<(), Box<dyn Error>> {
-    part2::main()
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [] | ["part2"] => part2::main(),
+        ["part1"] => part1::main(),
+        ["table"] => {
+            print!("{}", composed_map("seed", "location")?);
+            Ok(())
+        }
+        ["table", from, to] => {
+            print!("{}", composed_map(from, to)?);
+            Ok(())
+        }
+        ["lookup", from, to, value] => {
+            let value: u64 = value.parse()?;
+            let map = composed_map(from, to)?;
+            println!(
+                "{} {} maps to {} {}",
+                from,
+                value,
+                to,
+                map.get(value).ok_or("Value outside of the map")?
+            );
+            Ok(())
+        }
+        ["check"] => {
+            let content = fs::read_to_string("input.txt")?;
+            Almanac::parse_strict(&content)?;
+            println!("Almanac is valid");
+            Ok(())
+        }
+        ["seed-for", location] => {
+            let location: u64 = location.parse()?;
+            let seeds = composed_map("seed", "location")?
+                .inverse()
+                .preimages(location);
+            if seeds.is_empty() {
+                println!("No seed reaches location {}", location);
+            }
+            for seed in seeds {
+                println!("Seed {} reaches location {}", seed, location);
+            }
+            Ok(())
+        }
+        _ => Err(
+            "Usage: day-5 [part1 | part2 | check | table [<from> <to>] | lookup <from> <to> <value> | seed-for <location>]"
+                .into(),
+        ),
+    }
 }
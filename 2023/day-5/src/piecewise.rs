@@ -0,0 +1,261 @@
+use std::fmt;
+use std::ops::Range;
+
+use crate::range_map::RangeMap;
+
+/// A run of consecutive sources that all shift by the same offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    pub source: Range<u64>,
+    pub destination: u64,
+}
+
+impl Segment {
+    fn len(&self) -> u64 {
+        self.source.end - self.source.start
+    }
+
+    fn apply(&self, value: u64) -> u64 {
+        self.destination + value - self.source.start
+    }
+
+    fn offset(&self) -> i128 {
+        self.destination as i128 - self.source.start as i128
+    }
+
+    fn destination_range(&self) -> Range<u64> {
+        self.destination..self.destination + self.len()
+    }
+}
+
+/// Several almanac layers collapsed into one piecewise-linear function.
+/// Segments are sorted by source, disjoint, and adjacent segments with the
+/// same offset are merged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    segments: Vec<Segment>,
+}
+
+impl PiecewiseMap {
+    /// Maps every value to itself, except `u64::MAX`: segments are half-open
+    /// ranges, so they stop one short of it. Almanac values never get that
+    /// large, and `get(u64::MAX)` returns `None`.
+    pub fn identity() -> PiecewiseMap {
+        PiecewiseMap {
+            segments: vec![Segment {
+                source: 0..u64::MAX,
+                destination: 0,
+            }],
+        }
+    }
+
+    /// Composes the layers in order, e.g. the seven almanac maps into a single
+    /// `seed -> location` function.
+    pub fn compose<'a>(layers: impl IntoIterator<Item = &'a RangeMap>) -> PiecewiseMap {
+        layers
+            .into_iter()
+            .fold(PiecewiseMap::identity(), |map, layer| map.then(layer))
+    }
+
+    /// Returns the function that applies `self` first and `layer` second.
+    pub fn then(&self, layer: &RangeMap) -> PiecewiseMap {
+        let segments =
+            self.segments
+                .iter()
+                .flat_map(|segment| {
+                    layer.split(segment.destination_range()).into_iter().map(
+                        |(middle, destination)| {
+                            let start = segment.source.start + (middle.start - segment.destination);
+                            Segment {
+                                source: start..start + (middle.end - middle.start),
+                                destination,
+                            }
+                        },
+                    )
+                })
+                .collect();
+        PiecewiseMap::new(segments)
+    }
+
+    fn new(mut segments: Vec<Segment>) -> PiecewiseMap {
+        segments.sort_by_key(|segment| segment.source.start);
+        let mut merged: Vec<Segment> = Vec::with_capacity(segments.len());
+        for segment in segments {
+            match merged.last_mut() {
+                Some(last)
+                    if last.source.end == segment.source.start
+                        && last.offset() == segment.offset() =>
+                {
+                    last.source.end = segment.source.end;
+                }
+                _ => merged.push(segment),
+            }
+        }
+        PiecewiseMap { segments: merged }
+    }
+
+    /// Looks `value` up by binary search. Returns `None` if no segment covers
+    /// it, which for composed maps only happens for `u64::MAX`.
+    pub fn get(&self, value: u64) -> Option<u64> {
+        let idx = self
+            .segments
+            .partition_point(|segment| segment.source.end <= value);
+        self.segments
+            .get(idx)
+            .filter(|segment| segment.source.start <= value)
+            .map(|segment| segment.apply(value))
+    }
+
+    /// Builds the inverse lookup, e.g. to ask which seeds reach a given
+    /// location.
+    pub fn inverse(&self) -> InverseMap {
+        InverseMap::new(&self.segments)
+    }
+}
+
+/// The inverse of a `PiecewiseMap`. Several segments can share a
+/// destination, so the destination axis is cut at every segment boundary
+/// into disjoint pieces, sorted by start, each listing where its first value
+/// comes from in every segment that covers it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InverseMap {
+    pieces: Vec<(Range<u64>, Vec<u64>)>,
+}
+
+impl InverseMap {
+    fn new(segments: &[Segment]) -> InverseMap {
+        let mut bounds: Vec<u64> = segments
+            .iter()
+            .flat_map(|segment| [segment.destination, segment.destination_range().end])
+            .collect();
+        bounds.sort_unstable();
+        bounds.dedup();
+        let mut pieces: Vec<(Range<u64>, Vec<u64>)> = bounds
+            .windows(2)
+            .map(|pair| (pair[0]..pair[1], vec![]))
+            .collect();
+        for segment in segments {
+            let first = bounds.partition_point(|bound| *bound < segment.destination);
+            let last = bounds.partition_point(|bound| *bound < segment.destination_range().end);
+            for (range, sources) in &mut pieces[first..last] {
+                sources.push(segment.source.start + (range.start - segment.destination));
+            }
+        }
+        pieces.retain(|(_, sources)| !sources.is_empty());
+        for (_, sources) in pieces.iter_mut() {
+            sources.sort_unstable();
+        }
+        InverseMap { pieces }
+    }
+
+    /// Every source that maps to `value`, in increasing order, found by
+    /// binary search over the pieces.
+    pub fn preimages(&self, value: u64) -> Vec<u64> {
+        let idx = self.pieces.partition_point(|(range, _)| range.end <= value);
+        self.pieces
+            .get(idx)
+            .filter(|(range, _)| range.start <= value)
+            .map(|(range, sources)| {
+                sources
+                    .iter()
+                    .map(|source| source + (value - range.start))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>20} {:>20} {:>20} {:>21}",
+            "source start", "source end", "destination start", "offset"
+        )?;
+        for segment in self.segments.iter() {
+            writeln!(
+                f,
+                "{:>20} {:>20} {:>20} {:>+21}",
+                segment.source.start,
+                segment.source.end,
+                segment.destination,
+                segment.offset()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::range_map::MapEntry;
+
+    fn layer(entries: &[(u64, u64, u64)]) -> RangeMap {
+        RangeMap::new(
+            entries
+                .iter()
+                .map(|(destination, source, range)| MapEntry {
+                    source_range: *source..*source + *range,
+                    destination_range: *destination..*destination + *range,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_compose_matches_layers() {
+        let layers = vec![
+            layer(&[(50, 98, 2), (52, 50, 48)]),
+            layer(&[(0, 15, 37), (37, 52, 2), (39, 0, 15)]),
+        ];
+        let map = PiecewiseMap::compose(&layers);
+        for seed in 0..200 {
+            let expected = layers.iter().fold(seed, |value, layer| {
+                layer.map_range(value..value + 1)[0].start
+            });
+            assert_eq!(Some(expected), map.get(seed));
+        }
+    }
+
+    #[test]
+    fn test_compose_merges_segments() {
+        let swap = layer(&[(10, 0, 5), (0, 10, 5)]);
+        let layers = vec![swap.clone(), swap];
+        assert_eq!(PiecewiseMap::identity(), PiecewiseMap::compose(&layers));
+    }
+
+    #[test]
+    fn test_preimages() {
+        let map = PiecewiseMap::compose(&[layer(&[(50, 98, 2), (52, 50, 48)])]).inverse();
+        assert_eq!(vec![98], map.preimages(50));
+        assert_eq!(vec![50], map.preimages(52));
+        assert_eq!(vec![10], map.preimages(10));
+
+        let collapsing = PiecewiseMap::compose(&[layer(&[(0, 10, 5)])]).inverse();
+        assert_eq!(vec![2, 12], collapsing.preimages(2));
+        assert_eq!(Vec::<u64>::new(), collapsing.preimages(12));
+        assert_eq!(None, PiecewiseMap::identity().get(u64::MAX));
+        assert_eq!(
+            Vec::<u64>::new(),
+            PiecewiseMap::identity().inverse().preimages(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn test_preimages_match_forward() {
+        let layers = vec![
+            layer(&[(50, 98, 2), (52, 50, 48)]),
+            layer(&[(0, 15, 37), (37, 52, 2), (39, 0, 15)]),
+            layer(&[(20, 0, 30), (10, 40, 25)]),
+        ];
+        let map = PiecewiseMap::compose(&layers);
+        let inverse = map.inverse();
+        for value in 0..200 {
+            let expected: Vec<u64> = (0..200)
+                .filter(|seed| map.get(*seed) == Some(value))
+                .collect();
+            assert_eq!(expected, inverse.preimages(value));
+        }
+    }
+}
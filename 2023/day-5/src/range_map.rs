@@ -0,0 +1,147 @@
+use std::ops::Range;
+
+use crate::custom_error::AocError;
+
+#[derive(Debug, Clone)]
+pub struct MapEntry {
+    pub source_range: Range<u64>,
+    pub destination_range: Range<u64>,
+}
+
+impl MapEntry {
+    fn translate(&self, value: u64) -> u64 {
+        self.destination_range.start + value - self.source_range.start
+    }
+}
+
+/// Parses a `destination source range` line; `line_number` is only used for
+/// error reporting.
+pub fn parse_map_entry(line_number: usize, line: &str) -> Result<MapEntry, AocError> {
+    let malformed = |reason: String| AocError::MalformedEntry {
+        line: line_number,
+        content: line.to_string(),
+        reason,
+    };
+
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    if parts.len() != 3 {
+        return Err(malformed(format!(
+            "expected 3 numbers, found {}",
+            parts.len()
+        )));
+    }
+    let numbers = parts
+        .iter()
+        .map(|part| {
+            part.parse::<u64>()
+                .map_err(|e| malformed(format!("`{}`: {}", part, e)))
+        })
+        .collect::<Result<Vec<u64>, AocError>>()?;
+    let (destination, source, range) = (numbers[0], numbers[1], numbers[2]);
+
+    match (source.checked_add(range), destination.checked_add(range)) {
+        (Some(source_end), Some(destination_end)) => Ok(MapEntry {
+            source_range: source..source_end,
+            destination_range: destination..destination_end,
+        }),
+        _ => Err(AocError::EntryOverflow {
+            line: line_number,
+            content: line.to_string(),
+        }),
+    }
+}
+
+/// One layer of the almanac. Maps whole ranges at once by splitting them on
+/// entry boundaries instead of translating every value separately.
+#[derive(Debug, Clone)]
+pub struct RangeMap {
+    entries: Vec<MapEntry>,
+}
+
+impl RangeMap {
+    pub fn new(mut entries: Vec<MapEntry>) -> RangeMap {
+        entries.sort_by_key(|entry| entry.source_range.start);
+        RangeMap { entries }
+    }
+
+    /// Splits `range` on entry boundaries, returning each piece together with
+    /// the destination its start maps to. Values not covered by any entry map
+    /// to themselves. If entries overlap, the one with the lowest source start
+    /// wins.
+    pub fn split(&self, range: Range<u64>) -> Vec<(Range<u64>, u64)> {
+        let mut res = Vec::new();
+        let mut cursor = range.start;
+        for entry in self.entries.iter() {
+            if cursor >= range.end || entry.source_range.start >= range.end {
+                break;
+            }
+            if entry.source_range.end <= cursor {
+                continue;
+            }
+            if cursor < entry.source_range.start {
+                res.push((cursor..entry.source_range.start, cursor));
+                cursor = entry.source_range.start;
+            }
+            let end = range.end.min(entry.source_range.end);
+            res.push((cursor..end, entry.translate(cursor)));
+            cursor = end;
+        }
+        if cursor < range.end {
+            res.push((cursor..range.end, cursor));
+        }
+        res
+    }
+
+    /// Maps `range` to the set of destination ranges it covers.
+    pub fn map_range(&self, range: Range<u64>) -> Vec<Range<u64>> {
+        self.split(range)
+            .into_iter()
+            .map(|(source, destination)| destination..destination + (source.end - source.start))
+            .collect()
+    }
+
+    pub fn map_ranges(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        ranges
+            .iter()
+            .flat_map(|range| self.map_range(range.clone()))
+            .collect()
+    }
+}
+
+/// Pushes `ranges` through every layer and returns the lowest value reached.
+pub fn min_destination<'a>(
+    maps: impl IntoIterator<Item = &'a RangeMap>,
+    ranges: Vec<Range<u64>>,
+) -> Option<u64> {
+    maps.into_iter()
+        .fold(ranges, |ranges, map| map.map_ranges(&ranges))
+        .iter()
+        .map(|r| r.start)
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(destination: u64, source: u64, range: u64) -> MapEntry {
+        MapEntry {
+            source_range: source..source + range,
+            destination_range: destination..destination + range,
+        }
+    }
+
+    #[test]
+    fn test_map_range_splits_on_boundaries() {
+        let map = RangeMap::new(vec![entry(50, 98, 2), entry(52, 50, 48)]);
+        assert_eq!(vec![0..10], map.map_range(0..10));
+        assert_eq!(vec![40..50, 52..62], map.map_range(40..60));
+        assert_eq!(vec![97..100, 50..52, 100..105], map.map_range(95..105));
+    }
+
+    #[test]
+    fn test_map_range_overlapping_entries() {
+        let map = RangeMap::new(vec![entry(100, 0, 10), entry(200, 5, 10)]);
+        assert_eq!(vec![106..110, 205..210], map.map_range(6..15));
+    }
+}
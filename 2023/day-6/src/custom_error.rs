@@ -6,4 +6,12 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("invalid input: {0}")]
+    #[diagnostic(code(aoc::invalid_input))]
+    InvalidInput(String),
+
+    #[error("race with time {time} and distance {distance} overflows u128")]
+    #[diagnostic(code(aoc::overflow))]
+    Overflow { time: u128, distance: u128 },
 }
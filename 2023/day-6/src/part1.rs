@@ -1,49 +1,36 @@
 use crate::custom_error::AocError;
-
-fn count_solutions(time_limit: u32, distance: u32) -> u32 {
-    (0..time_limit)
-        .filter_map(|time_pressing| {
-            if time_pressing * (time_limit - time_pressing) > distance {
-                Some(1)
-            } else {
-                None
-            }
-        })
-        .sum()
-}
+use crate::race::count_solutions;
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<u32, AocError> {
+pub fn process(input: &str) -> miette::Result<u128, AocError> {
     let mut lines = input.lines();
     let time_line = lines.next().unwrap_or("");
     let distance_line = lines.next().unwrap_or("");
 
-    let times: Vec<u32> = time_line
+    let times: Vec<u128> = time_line
         .split_whitespace()
         .skip(1)
         .filter_map(|s| s.parse().ok())
         .collect();
 
-    let distances: Vec<u32> = distance_line
+    let distances: Vec<u128> = distance_line
         .split_whitespace()
         .skip(1)
         .filter_map(|s| s.parse().ok())
         .collect();
 
-    let tuples: Vec<(u32, u32)> = times.into_iter().zip(distances.into_iter()).collect();
-
-    let res: u32 = tuples
-        .iter()
-        .map(|(time_limit, distance)| count_solutions(*time_limit, *distance))
-        .fold(1, |acc, x| acc * x);
-
-    Ok(res)
+    times
+        .into_iter()
+        .zip(distances)
+        .try_fold(1u128, |acc, (time_limit, distance)| {
+            acc.checked_mul(count_solutions(time_limit, distance)?)
+                .ok_or(AocError::Overflow {
+                    time: time_limit,
+                    distance,
+                })
+        })
 }
 
-// t_t * t_p - t_t - t_p + 1 >= d - t_l + 1
-// (t_t - 1)*(t_p - 1) >= d - t_l + 1 >= 0
-// cont solutions
-
 #[cfg(test)]
 mod tests {
     use super::*;
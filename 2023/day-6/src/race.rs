@@ -0,0 +1,108 @@
+use std::ops::RangeInclusive;
+
+use crate::custom_error::AocError;
+
+/// Hold times `t` that beat the record, i.e. `t * (time - t) > distance`.
+///
+/// The winning times are the integers strictly between the roots of
+/// `t^2 - time * t + distance`. The roots are located with an integer square
+/// root of the discriminant and then nudged onto the exact boundary, so no
+/// floating point rounding is involved.
+pub fn winning_hold_times(
+    time: u128,
+    distance: u128,
+) -> Result<Option<RangeInclusive<u128>>, AocError> {
+    let overflow = || AocError::Overflow { time, distance };
+    let time_squared = time.checked_mul(time).ok_or_else(overflow)?;
+    let four_distance = distance.checked_mul(4).ok_or_else(overflow)?;
+    let Some(discriminant) = time_squared.checked_sub(four_distance) else {
+        return Ok(None);
+    };
+
+    // Cannot overflow: t * (time - t) <= time^2 / 4
+    let beats = |t: u128| t * (time - t) > distance;
+    let half = time / 2;
+    let mut low = (time - discriminant.isqrt()) / 2;
+    while low > 0 && beats(low - 1) {
+        low -= 1;
+    }
+    while low <= half && !beats(low) {
+        low += 1;
+    }
+    if low > half {
+        return Ok(None);
+    }
+    Ok(Some(low..=time - low))
+}
+
+/// Number of winning hold times.
+pub fn count_solutions(time: u128, distance: u128) -> Result<u128, AocError> {
+    Ok(winning_hold_times(time, distance)?
+        .map(|hold_times| hold_times.end() - hold_times.start() + 1)
+        .unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+
+    fn count_solutions_by_loop(time_limit: u64, distance: u64) -> u64 {
+        (0..time_limit)
+            .filter(|time_pressing| time_pressing * (time_limit - time_pressing) > distance)
+            .count() as u64
+    }
+
+    #[rstest]
+    #[case(7, 9, Some(2..=5))]
+    #[case(15, 40, Some(4..=11))]
+    #[case(30, 200, Some(11..=19))]
+    #[case(71530, 940200, Some(14..=71516))]
+    #[case(4, 4, None)]
+    #[case(0, 0, None)]
+    fn test_winning_hold_times(
+        #[case] time: u128,
+        #[case] distance: u128,
+        #[case] expected: Option<RangeInclusive<u128>>,
+    ) -> miette::Result<()> {
+        assert_eq!(expected, winning_hold_times(time, distance)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_distance_beyond_u64() -> miette::Result<()> {
+        let time = 1 << 40;
+        assert_eq!(
+            Some(1 << 39..=1 << 39),
+            winning_hold_times(time, (1 << 78) - 1)?
+        );
+        assert_eq!(None, winning_hold_times(time, 1 << 78)?);
+        assert!(winning_hold_times(u128::MAX, 1).is_err());
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn closed_form_matches_loop(time in 0u64..2_000, distance in 0u64..1_000_000) {
+            prop_assert_eq!(
+                count_solutions_by_loop(time, distance) as u128,
+                count_solutions(time as u128, distance as u128).unwrap()
+            );
+        }
+
+        #[test]
+        fn interval_is_exact(time in 0u128..1 << 60, distance in 0u128..1 << 110) {
+            let beats = |t: u128| t * (time - t) > distance;
+            match winning_hold_times(time, distance).unwrap() {
+                Some(hold_times) => {
+                    prop_assert!(beats(*hold_times.start()) && beats(*hold_times.end()));
+                    prop_assert!(*hold_times.start() == 0 || !beats(hold_times.start() - 1));
+                    prop_assert!(*hold_times.end() == time || !beats(hold_times.end() + 1));
+                }
+                None => prop_assert!(!beats(time / 2)),
+            }
+        }
+    }
+}
@@ -1,15 +1,13 @@
-use criterion::{
-    criterion_group, criterion_main, Criterion,
-};
+use aoc_utils::rng::Lcg;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use day_7::camel_cards::{Card, Hand, Ruleset, Wildcards};
 use day_7::*;
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
     let input = include_str!("../input1.txt");
 
     let mut group = c.benchmark_group("day_7::part1");
-    group.bench_with_input("part1", input, |b, input| {
-        b.iter(|| part1::process(input))
-    });
+    group.bench_with_input("part1", input, |b, input| b.iter(|| part1::process(input)));
 
     group.finish();
 }
@@ -18,8 +16,69 @@ fn criterion_benchmark_part2(c: &mut Criterion) {
     let input = include_str!("../input2.txt");
 
     let mut group = c.benchmark_group("day_7::part2");
-    group.bench_with_input("part2", input, |b, input| {
-        b.iter(|| part2::process(input))
+    group.bench_with_input("part2", input, |b, input| b.iter(|| part2::process(input)));
+
+    group.finish();
+}
+
+/// Deterministic pseudo-random hands, so runs are comparable.
+fn generate_hands(count: usize) -> Vec<Hand> {
+    const CARDS: [Card; 13] = [
+        Card::N(2),
+        Card::N(3),
+        Card::N(4),
+        Card::N(5),
+        Card::N(6),
+        Card::N(7),
+        Card::N(8),
+        Card::N(9),
+        Card::T,
+        Card::J,
+        Card::Q,
+        Card::K,
+        Card::A,
+    ];
+    let mut rng = Lcg::default();
+    let mut next_card = || CARDS[rng.below(CARDS.len())];
+    (0..count)
+        .map(|_| Hand {
+            cards: [
+                next_card(),
+                next_card(),
+                next_card(),
+                next_card(),
+                next_card(),
+            ],
+        })
+        .collect()
+}
+
+fn criterion_benchmark_sorting(c: &mut Criterion) {
+    let hands = generate_hands(1_000_000);
+    let ruleset = Wildcards::jokers();
+
+    let mut group = c.benchmark_group("day_7::sorting");
+    group.sample_size(10);
+    group.bench_function("compare", |b| {
+        b.iter_batched(
+            || hands.clone(),
+            |mut hands| hands.sort_by(|hand, other| ruleset.compare(hand, other)),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("sort_key", |b| {
+        b.iter_batched(
+            || hands.clone(),
+            |hands| {
+                let mut keyed: Vec<(u32, Hand)> = hands
+                    .into_iter()
+                    .map(|hand| (ruleset.sort_key(&hand), hand))
+                    .collect();
+                keyed.sort_unstable_by_key(|(key, _)| *key);
+                keyed.into_iter().map(|(_, hand)| hand).collect::<Vec<_>>()
+            },
+            BatchSize::LargeInput,
+        )
     });
 
     group.finish();
@@ -28,6 +87,7 @@ fn criterion_benchmark_part2(c: &mut Criterion) {
 criterion_group!(
     benches,
     criterion_benchmark_part1,
-    criterion_benchmark_part2
+    criterion_benchmark_part2,
+    criterion_benchmark_sorting
 );
 criterion_main!(benches);
@@ -1,4 +1,6 @@
+use day_7::camel_cards::Wildcards;
 use day_7::part2::process;
+use day_7::report::{render, standings, ReportFormat};
 use miette::Context;
 
 #[cfg(feature = "dhat-heap")]
@@ -14,6 +16,14 @@ fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
     let file = include_str!("../../input2.txt");
+    // `--format table|csv|json` prints the full standings instead of the total
+    let format = std::env::args().skip_while(|arg| arg != "--format").nth(1);
+    if let Some(format) = format {
+        let format: ReportFormat = format.parse()?;
+        let standings = standings(file, &Wildcards::jokers()).context("rank part 2")?;
+        print!("{}", render(&standings, format)?);
+        return Ok(());
+    }
     let result = process(file).context("process part 2")?;
     println!("{}", result);
     Ok(())
@@ -0,0 +1,451 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+use std::{collections::HashMap, hash::RandomState};
+
+use crate::custom_error::AocError;
+use itertools::Itertools;
+use nom::{
+    bytes::complete::take_till1,
+    character::complete::{self, one_of, space0},
+    combinator::{all_consuming, opt},
+    multi::count,
+    sequence::{pair, terminated},
+    IResult, Parser,
+};
+use serde::{de, Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum Card {
+    A,
+    K,
+    Q,
+    J,
+    T,
+    N(u8),
+}
+
+impl Card {
+    fn from_char(c: char) -> Option<Card> {
+        match c {
+            'A' => Some(Card::A),
+            'K' => Some(Card::K),
+            'Q' => Some(Card::Q),
+            'J' => Some(Card::J),
+            'T' => Some(Card::T),
+            '2'..='9' => c.to_digit(10).map(|n| Card::N(n as u8)),
+            _ => None,
+        }
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Card::A => 'A',
+            Card::K => 'K',
+            Card::Q => 'Q',
+            Card::J => 'J',
+            Card::T => 'T',
+            Card::N(n) => std::char::from_digit(n as u32, 10).unwrap(),
+        }
+    }
+
+    /// Face value order, from `2` (0) to `A` (12).
+    pub fn natural_rank(&self) -> u8 {
+        match self {
+            Card::N(n) => n - 2,
+            Card::T => 8,
+            Card::J => 9,
+            Card::Q => 10,
+            Card::K => 11,
+            Card::A => 12,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Hand {
+    pub cards: [Card; 5],
+}
+
+impl std::fmt::Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.cards.iter().map(|c| c.to_char()).join(""))
+    }
+}
+
+fn card(input: &str) -> IResult<&str, Card> {
+    one_of("AKQJT23456789")
+        .map(|c| Card::from_char(c).unwrap())
+        .parse(input)
+}
+
+/// Parses exactly five cards.
+pub fn hand(input: &str) -> IResult<&str, Hand> {
+    count(card, 5)
+        .map(|cards| Hand {
+            cards: [cards[0], cards[1], cards[2], cards[3], cards[4]],
+        })
+        .parse(input)
+}
+
+impl FromStr for Hand {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Hand, AocError> {
+        if let Ok((_, hand)) = all_consuming(hand)(s) {
+            return Ok(hand);
+        }
+        if let Some(card) = s.chars().find(|c| Card::from_char(*c).is_none()) {
+            return Err(AocError::InvalidCard {
+                card,
+                hand: s.to_string(),
+            });
+        }
+        Err(AocError::WrongHandLength {
+            hand: s.to_string(),
+            length: s.chars().count(),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Hand {
+    fn deserialize<D>(deserializer: D) -> Result<Hand, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for Hand {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// A hand followed by its bid, e.g. `32T3K 765`.
+fn line(input: &str) -> IResult<&str, (&str, Option<u64>)> {
+    pair(
+        terminated(take_till1(|c: char| c.is_whitespace()), space0),
+        opt(complete::u64),
+    )(input)
+}
+
+fn parse_line(line_number: usize, input: &str) -> Result<(Hand, u64), AocError> {
+    let (rest, (cards, bid)) =
+        line(input.trim()).map_err(|_| AocError::MissingBid { line: line_number })?;
+    let hand = cards.parse().map_err(|error| AocError::InvalidHand {
+        line: line_number,
+        error: Box::new(error),
+    })?;
+    match bid {
+        Some(bid) if rest.is_empty() => Ok((hand, bid)),
+        Some(_) => Err(AocError::InvalidBid {
+            line: line_number,
+            content: input.to_string(),
+        }),
+        None if rest.is_empty() => Err(AocError::MissingBid { line: line_number }),
+        None => Err(AocError::InvalidBid {
+            line: line_number,
+            content: input.to_string(),
+        }),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Serialize)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+struct HandCounts<'a>(HashMap<&'a Card, usize, RandomState>);
+
+impl HandCounts<'_> {
+    fn is_five_of_a_kind(&self) -> bool {
+        self.0.values().any(|v| *v == 5)
+    }
+
+    fn is_four_of_a_kind(&self) -> bool {
+        self.0.values().any(|v| *v == 4)
+    }
+
+    fn is_full_house(&self) -> bool {
+        self.0.values().any(|v| *v == 3) && self.0.values().any(|v| *v == 2)
+    }
+
+    fn is_three_of_a_kind(&self) -> bool {
+        self.0.values().any(|v| *v == 3) && self.0.values().filter(|v| **v == 2).count() == 0
+    }
+
+    fn is_two_pair(&self) -> bool {
+        self.0.values().filter(|v| **v == 2).count() == 2
+    }
+
+    fn is_one_pair(&self) -> bool {
+        self.0.values().filter(|v| **v == 2).count() == 1
+    }
+
+    fn get_hand_type(&self) -> HandType {
+        if self.is_five_of_a_kind() {
+            HandType::FiveOfAKind
+        } else if self.is_four_of_a_kind() {
+            HandType::FourOfAKind
+        } else if self.is_full_house() {
+            HandType::FullHouse
+        } else if self.is_three_of_a_kind() {
+            HandType::ThreeOfAKind
+        } else if self.is_two_pair() {
+            HandType::TwoPair
+        } else if self.is_one_pair() {
+            HandType::OnePair
+        } else {
+            HandType::HighCard
+        }
+    }
+}
+
+/// The rules that differ between variants of Camel Cards.
+pub trait Ruleset {
+    /// Strength of a single card when breaking ties, higher is stronger.
+    /// Must be below 16 so that it fits in a `sort_key` nibble.
+    fn card_rank(&self, card: Card) -> u8;
+
+    /// Whether `card` stands in for whatever card makes the strongest hand.
+    fn is_wildcard(&self, _card: Card) -> bool {
+        false
+    }
+
+    /// Type of the hand, with wildcards joining the most common other card.
+    fn hand_type(&self, hand: &Hand) -> HandType {
+        let mut counts = hand
+            .cards
+            .iter()
+            .filter(|card| !self.is_wildcard(**card))
+            .counts();
+        let wildcard_count = 5 - counts.values().sum::<usize>();
+        let max_count_key = counts.iter().max_by_key(|(_, v)| *v).map(|(k, _)| *k);
+        match max_count_key {
+            Some(max_count_key) => {
+                *counts.get_mut(max_count_key).unwrap() += wildcard_count;
+                HandCounts(counts).get_hand_type()
+            }
+            None => HandType::FiveOfAKind,
+        }
+    }
+
+    /// Packs the hand type and the five card ranks into one integer, so
+    /// that hands order by type and then card by card. This is the one place
+    /// that decides the order: `compare` and `rank_hands` both go through it.
+    fn sort_key(&self, hand: &Hand) -> u32 {
+        hand.cards
+            .iter()
+            .fold(self.hand_type(hand) as u32, |key, card| {
+                key << 4 | self.card_rank(*card) as u32
+            })
+    }
+
+    fn compare(&self, hand: &Hand, other: &Hand) -> Ordering {
+        self.sort_key(hand).cmp(&self.sort_key(other))
+    }
+}
+
+/// Part 1 rules: `J` is a jack, cards rank by face value.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Standard;
+
+impl Ruleset for Standard {
+    fn card_rank(&self, card: Card) -> u8 {
+        card.natural_rank()
+    }
+}
+
+/// Wildcard rules: the given cards are wild and rank below every other card.
+/// Part 2 is `Wildcards::jokers()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wildcards {
+    pub cards: Vec<Card>,
+}
+
+impl Wildcards {
+    pub fn jokers() -> Wildcards {
+        Wildcards {
+            cards: vec![Card::J],
+        }
+    }
+}
+
+impl Ruleset for Wildcards {
+    fn card_rank(&self, card: Card) -> u8 {
+        let is_wildcard = self.is_wildcard(card);
+        let below = self
+            .cards
+            .iter()
+            .filter(|wildcard| wildcard.natural_rank() < card.natural_rank())
+            .count() as u8;
+        if is_wildcard {
+            below
+        } else {
+            self.cards.len() as u8 + card.natural_rank() - below
+        }
+    }
+
+    fn is_wildcard(&self, card: Card) -> bool {
+        self.cards.contains(&card)
+    }
+}
+
+/// Parses the hands with their bids and orders them from weakest to
+/// strongest under `ruleset`, so a hand's rank is its index plus one.
+pub fn rank_hands(input: &str, ruleset: &impl Ruleset) -> Result<Vec<(Hand, u64)>, AocError> {
+    let mut keyed = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            let (hand, value) = parse_line(idx + 1, line)?;
+            Ok((ruleset.sort_key(&hand), hand, value))
+        })
+        .collect::<Result<Vec<_>, AocError>>()?;
+    keyed.sort_unstable_by_key(|(key, _, _)| *key);
+    Ok(keyed
+        .into_iter()
+        .map(|(_, hand, value)| (hand, value))
+        .collect())
+}
+
+/// What the hand at `idx` in the output of `rank_hands` wins: its bid
+/// multiplied by its rank.
+pub fn winnings(idx: usize, bid: u64) -> Result<u64, AocError> {
+    bid.checked_mul(idx as u64 + 1)
+        .ok_or(AocError::WinningsOverflow)
+}
+
+/// Sum of every hand's bid multiplied by its rank under `ruleset`.
+pub fn total_winnings(input: &str, ruleset: &impl Ruleset) -> Result<u64, AocError> {
+    rank_hands(input, ruleset)?
+        .into_iter()
+        .enumerate()
+        .try_fold(0u64, |acc, (idx, (_, value))| {
+            acc.checked_add(winnings(idx, value)?)
+                .ok_or(AocError::WinningsOverflow)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn hand(s: &str) -> Hand {
+        s.parse().unwrap()
+    }
+
+    #[rstest]
+    #[case("32T3K", HandType::OnePair, HandType::OnePair)]
+    #[case("KK677", HandType::TwoPair, HandType::TwoPair)]
+    #[case("KTJJT", HandType::TwoPair, HandType::FourOfAKind)]
+    #[case("T55J5", HandType::ThreeOfAKind, HandType::FourOfAKind)]
+    #[case("JJJJJ", HandType::FiveOfAKind, HandType::FiveOfAKind)]
+    #[case("2345J", HandType::HighCard, HandType::OnePair)]
+    fn test_hand_type(#[case] cards: &str, #[case] standard: HandType, #[case] jokers: HandType) {
+        assert_eq!(standard, Standard.hand_type(&hand(cards)));
+        assert_eq!(jokers, Wildcards::jokers().hand_type(&hand(cards)));
+    }
+
+    #[test]
+    fn test_multiple_wildcard_ranks() {
+        let ruleset = Wildcards {
+            cards: vec![Card::J, Card::N(2)],
+        };
+        assert_eq!(HandType::FiveOfAKind, ruleset.hand_type(&hand("2JJ2A")));
+        assert_eq!(0, ruleset.card_rank(Card::N(2)));
+        assert_eq!(1, ruleset.card_rank(Card::J));
+        assert_eq!(2, ruleset.card_rank(Card::N(3)));
+        assert_eq!(12, ruleset.card_rank(Card::A));
+        assert_eq!(
+            Ordering::Less,
+            ruleset.compare(&hand("2JJJJ"), &hand("J2222"))
+        );
+    }
+
+    #[rstest]
+    #[case("32T3K 765", "")]
+    #[case("32T3X 765", "line 1: invalid card `X` in hand `32T3X`")]
+    #[case("32T3 765", "line 1: hand `32T3` has 4 cards, expected 5")]
+    #[case("32T3KK 765", "line 1: hand `32T3KK` has 6 cards, expected 5")]
+    #[case("32T3K", "line 1: missing bid")]
+    #[case("32T3K x", "line 1: invalid bid `32T3K x`")]
+    #[case("32T3K 76 5", "line 1: invalid bid `32T3K 76 5`")]
+    fn test_parse_line(#[case] input: &str, #[case] error: &str) {
+        match parse_line(1, input) {
+            Ok((hand, bid)) => {
+                assert_eq!("32T3K", hand.to_string());
+                assert_eq!(765, bid);
+            }
+            Err(e) => assert_eq!(error, e.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_winnings_overflow() -> miette::Result<()> {
+        let max = u64::MAX;
+        assert!(matches!(
+            total_winnings(&format!("32T3K {max}\nKK677 {max}"), &Standard),
+            Err(AocError::WinningsOverflow)
+        ));
+        assert!(matches!(
+            total_winnings(&format!("32T3K {max}\nKK677 1"), &Standard),
+            Err(AocError::WinningsOverflow)
+        ));
+        assert_eq!(max, total_winnings(&format!("32T3K {max}"), &Standard)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let hand: Hand = serde_json::from_str("\"KTJJT\"").unwrap();
+        assert_eq!("KTJJT", hand.to_string());
+        assert!(serde_json::from_str::<Hand>("\"KTJJ\"").is_err());
+    }
+
+    /// Type first, then the first card that differs.
+    fn type_then_cards(ruleset: &impl Ruleset, hand: &Hand, other: &Hand) -> Ordering {
+        ruleset
+            .hand_type(hand)
+            .cmp(&ruleset.hand_type(other))
+            .then_with(|| {
+                hand.cards
+                    .iter()
+                    .map(|card| ruleset.card_rank(*card))
+                    .cmp(other.cards.iter().map(|card| ruleset.card_rank(*card)))
+            })
+    }
+
+    #[test]
+    fn test_sort_key_orders_by_type_then_cards() {
+        let hands = [
+            "32T3K", "T55J5", "KK677", "KTJJT", "QQQJA", "JJJJJ", "2345J", "AAAA2",
+        ]
+        .map(hand);
+        let jokers = Wildcards::jokers();
+        for (hand, other) in hands.iter().cartesian_product(hands.iter()) {
+            assert_eq!(
+                type_then_cards(&Standard, hand, other),
+                Standard.compare(hand, other)
+            );
+            assert_eq!(
+                type_then_cards(&jokers, hand, other),
+                jokers.compare(hand, other)
+            );
+        }
+    }
+}
@@ -6,4 +6,45 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(code(aoc::json_error))]
+    JsonError(#[from] serde_json::Error),
+
+    #[error("unknown report format `{0}`")]
+    #[diagnostic(code(aoc::unknown_format), help("use `table`, `csv` or `json`"))]
+    UnknownFormat(String),
+
+    #[error("invalid card `{card}` in hand `{hand}`")]
+    #[diagnostic(
+        code(aoc::invalid_card),
+        help("cards are A, K, Q, J, T and 2 through 9")
+    )]
+    InvalidCard { card: char, hand: String },
+
+    #[error("hand `{hand}` has {length} cards, expected 5")]
+    #[diagnostic(code(aoc::wrong_hand_length))]
+    WrongHandLength { hand: String, length: usize },
+
+    #[error("line {line}: {error}")]
+    #[diagnostic(code(aoc::invalid_hand))]
+    InvalidHand { line: usize, error: Box<AocError> },
+
+    #[error("line {line}: missing bid")]
+    #[diagnostic(
+        code(aoc::missing_bid),
+        help("each line is a hand and a bid, e.g. `32T3K 765`")
+    )]
+    MissingBid { line: usize },
+
+    #[error("line {line}: invalid bid `{content}`")]
+    #[diagnostic(
+        code(aoc::invalid_bid),
+        help("each line is a hand and a bid, e.g. `32T3K 765`")
+    )]
+    InvalidBid { line: usize, content: String },
+
+    #[error("total winnings overflow u64")]
+    #[diagnostic(code(aoc::winnings_overflow))]
+    WinningsOverflow,
 }
@@ -1,4 +1,6 @@
+pub mod camel_cards;
 pub mod custom_error;
+pub mod report;
 
 pub mod part1;
 pub mod part2;
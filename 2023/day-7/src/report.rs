@@ -0,0 +1,148 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use crate::camel_cards::{rank_hands, winnings, Hand, HandType, Ruleset, Wildcards};
+use crate::custom_error::AocError;
+
+/// One row of the final standings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Standing {
+    pub rank: u64,
+    pub hand: Hand,
+    /// Type under the ruleset the standings were computed with.
+    pub hand_type: HandType,
+    /// Type under the part 2 rules, with jokers upgraded.
+    pub joker_type: HandType,
+    pub bid: u64,
+    pub contribution: u64,
+}
+
+/// Every hand with its rank and contribution to the total winnings,
+/// weakest first.
+pub fn standings(input: &str, ruleset: &impl Ruleset) -> Result<Vec<Standing>, AocError> {
+    let jokers = Wildcards::jokers();
+    rank_hands(input, ruleset)?
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (hand, bid))| {
+            Ok(Standing {
+                rank: idx as u64 + 1,
+                hand,
+                hand_type: ruleset.hand_type(&hand),
+                joker_type: jokers.hand_type(&hand),
+                bid,
+                contribution: winnings(idx, bid)?,
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+impl FromStr for ReportFormat {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<ReportFormat, AocError> {
+        match s {
+            "table" => Ok(ReportFormat::Table),
+            "csv" => Ok(ReportFormat::Csv),
+            "json" => Ok(ReportFormat::Json),
+            _ => Err(AocError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+pub fn render(standings: &[Standing], format: ReportFormat) -> Result<String, AocError> {
+    let mut out = String::new();
+    match format {
+        ReportFormat::Table => {
+            writeln!(
+                out,
+                "{:>6}  {:<5}  {:<14}  {:<14}  {:>6}  {:>12}",
+                "rank", "hand", "type", "joker type", "bid", "contribution"
+            )
+            .unwrap();
+            for s in standings {
+                writeln!(
+                    out,
+                    "{:>6}  {:<5}  {:<14}  {:<14}  {:>6}  {:>12}",
+                    s.rank,
+                    s.hand.to_string(),
+                    format!("{:?}", s.hand_type),
+                    format!("{:?}", s.joker_type),
+                    s.bid,
+                    s.contribution
+                )
+                .unwrap();
+            }
+        }
+        ReportFormat::Csv => {
+            writeln!(out, "rank,hand,hand_type,joker_type,bid,contribution").unwrap();
+            for s in standings {
+                writeln!(
+                    out,
+                    "{},{},{:?},{:?},{},{}",
+                    s.rank, s.hand, s.hand_type, s.joker_type, s.bid, s.contribution
+                )
+                .unwrap();
+            }
+        }
+        ReportFormat::Json => {
+            out = serde_json::to_string_pretty(standings)?;
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camel_cards::Standard;
+
+    const INPUT: &str = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+
+    #[test]
+    fn test_standings() -> miette::Result<()> {
+        let standings = standings(INPUT, &Standard)?;
+        let hands = standings
+            .iter()
+            .map(|s| s.hand.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["32T3K", "KTJJT", "KK677", "T55J5", "QQQJA"], hands);
+        assert_eq!(6440, standings.iter().map(|s| s.contribution).sum::<u64>());
+        assert_eq!(HandType::TwoPair, standings[1].hand_type);
+        assert_eq!(HandType::FourOfAKind, standings[1].joker_type);
+        Ok(())
+    }
+
+    #[test]
+    fn test_render() -> miette::Result<()> {
+        let standings = standings(INPUT, &Wildcards::jokers())?;
+        let csv = render(&standings, ReportFormat::Csv)?;
+        assert_eq!(
+            Some("5,KTJJT,FourOfAKind,FourOfAKind,220,1100"),
+            csv.lines().last()
+        );
+        let json = render(&standings[..1], ReportFormat::Json)?;
+        assert!(json.contains("\"hand\": \"32T3K\""));
+        assert!(json.contains("\"joker_type\": \"OnePair\""));
+        assert!("xml".parse::<ReportFormat>().is_err());
+        assert!(matches!(
+            super::standings(&format!("32T3K 1\nKK677 {}", u64::MAX), &Standard),
+            Err(AocError::WinningsOverflow)
+        ));
+        Ok(())
+    }
+}
@@ -1,10 +1,14 @@
+use std::collections::HashMap;
+
+use aoc_utils::rng::Lcg;
 use criterion::{
     criterion_group, criterion_main, Criterion,
 };
+use day_8::network::{Direction, Network};
 use day_8::*;
 
 fn criterion_benchmark_part1(c: &mut Criterion) {
-    let input = include_str!("../input1.txt");
+    let input = include_str!("../input.txt");
 
     let mut group = c.benchmark_group("day_8::part1");
     group.bench_with_input("part1", input, |b, input| {
@@ -15,7 +19,7 @@ fn criterion_benchmark_part1(c: &mut Criterion) {
 }
 
 fn criterion_benchmark_part2(c: &mut Criterion) {
-    let input = include_str!("../input2.txt");
+    let input = include_str!("../input.txt");
 
     let mut group = c.benchmark_group("day_8::part2");
     group.bench_with_input("part2", input, |b, input| {
@@ -25,9 +29,80 @@ fn criterion_benchmark_part2(c: &mut Criterion) {
     group.finish();
 }
 
+/// Deterministic pseudo-random network with `count` nodes, so runs are
+/// comparable. Every node links to two random nodes.
+fn generate_network(count: usize) -> String {
+    let mut rng = Lcg::default();
+    let mut input: String = (0..293)
+        .map(|_| if rng.below(2) == 0 { 'L' } else { 'R' })
+        .collect();
+    input.push_str("\n\n");
+    for idx in 0..count {
+        let (left, right) = (rng.below(count), rng.below(count));
+        input.push_str(&format!("N{idx} = (N{left}, N{right})\n"));
+    }
+    input
+}
+
+/// The string-keyed representation the network used before interning.
+fn hash_map_nodes<'a>(network: &Network<'a>) -> HashMap<&'a str, (&'a str, &'a str)> {
+    (0..network.len() as u32)
+        .map(|id| {
+            let left = network.step(id, Direction::L);
+            let right = network.step(id, Direction::R);
+            (network.name(id), (network.name(left), network.name(right)))
+        })
+        .collect()
+}
+
+fn walk_hash_map<'a>(
+    nodes: &HashMap<&'a str, (&'a str, &'a str)>,
+    directions: &[Direction],
+    steps: usize,
+) -> &'a str {
+    let mut current = "N0";
+    for direction in directions.iter().cycle().take(steps) {
+        let (left, right) = nodes[current];
+        current = match direction {
+            Direction::L => left,
+            Direction::R => right,
+        };
+    }
+    current
+}
+
+fn walk_interned(network: &Network, steps: usize) -> u32 {
+    let mut current = 0;
+    for direction in network.directions.iter().cycle().take(steps) {
+        current = network.step(current, *direction);
+    }
+    current
+}
+
+fn criterion_benchmark_walk(c: &mut Criterion) {
+    const STEPS: usize = 1_000_000;
+
+    let mut group = c.benchmark_group("day_8::walk");
+    group.sample_size(10);
+    for count in [1_000, 100_000] {
+        let input = generate_network(count);
+        let network = Network::parse(&input).expect("generated network parses");
+        let nodes = hash_map_nodes(&network);
+        group.bench_function(format!("hash_map/{count}"), |b| {
+            b.iter(|| walk_hash_map(&nodes, &network.directions, STEPS))
+        });
+        group.bench_function(format!("interned/{count}"), |b| {
+            b.iter(|| walk_interned(&network, STEPS))
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     criterion_benchmark_part1,
-    criterion_benchmark_part2
+    criterion_benchmark_part2,
+    criterion_benchmark_walk
 );
 criterion_main!(benches);
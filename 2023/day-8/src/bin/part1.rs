@@ -1,10 +1,18 @@
-use day_8::part1::process;
+use day_8::custom_error::AocError;
+use day_8::dot::to_dot;
+use day_8::network::Network;
+use day_8::part1::process_with;
+use day_8::selector::Selector;
 use miette::Context;
 
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
 static ALLOC: dhat::Alloc = dhat::Alloc;
 
+fn arg(name: &str) -> Option<String> {
+    std::env::args().skip_while(|arg| arg != name).nth(1)
+}
+
 #[tracing::instrument]
 fn main() -> miette::Result<()> {
     #[cfg(feature = "dhat-heap")]
@@ -14,7 +22,21 @@ fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
     let file = include_str!("../../input.txt");
-    let result = process(file).context("process part 1")?;
+    // `--start SELECTOR` and `--end SELECTOR` pick the nodes by name
+    let start: Selector = arg("--start").as_deref().unwrap_or("AAA").parse()?;
+    let end: Selector = arg("--end").as_deref().unwrap_or("ZZZ").parse()?;
+    // `--dot FILE` also writes the network as a Graphviz graph
+    if let Some(path) = arg("--dot") {
+        let network = Network::parse(file)?;
+        let dot = to_dot(
+            &network,
+            &start.select(&network),
+            &end.select(&network),
+            &[],
+        );
+        std::fs::write(path, dot).map_err(AocError::from)?;
+    }
+    let result = process_with(file, &start, &end).context("process part 1")?;
     println!("{}", result);
     Ok(())
 }
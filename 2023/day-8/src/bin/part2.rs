@@ -1,10 +1,18 @@
-use day_8::part2::process;
+use day_8::custom_error::AocError;
+use day_8::dot::to_dot;
+use day_8::ghost::{earliest_common_step, ghosts};
+use day_8::network::Network;
+use day_8::selector::Selector;
 use miette::Context;
 
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
 static ALLOC: dhat::Alloc = dhat::Alloc;
 
+fn arg(name: &str) -> Option<String> {
+    std::env::args().skip_while(|arg| arg != name).nth(1)
+}
+
 #[tracing::instrument]
 fn main() -> miette::Result<()> {
     #[cfg(feature = "dhat-heap")]
@@ -14,7 +22,31 @@ fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
     let file = include_str!("../../input.txt");
-    let result = process(file).context("process part 2")?;
+    // `--start SELECTOR` and `--end SELECTOR` pick the nodes by name
+    let start: Selector = arg("--start").as_deref().unwrap_or("suffix:A").parse()?;
+    let end: Selector = arg("--end").as_deref().unwrap_or("suffix:Z").parse()?;
+    let network = Network::parse(file)?;
+    let (starts, ends) = (start.select(&network), end.select(&network));
+    let ghosts = ghosts(&network, &starts, &ends);
+    // `--dot FILE` also writes the network as a Graphviz graph, even when
+    // the cycle analysis fails, since that is when the graph helps most
+    if let Some(path) = arg("--dot") {
+        // Colour the cycle each ghost ends up in
+        let cycles = match &ghosts {
+            Ok(ghosts) => starts
+                .iter()
+                .zip(ghosts)
+                .map(|(start, ghost)| ghost.cycle_edges(&network, start))
+                .collect::<Vec<_>>(),
+            Err(error) => {
+                tracing::warn!("writing the graph without cycles: {error}");
+                vec![]
+            }
+        };
+        let dot = to_dot(&network, &starts, &ends, &cycles);
+        std::fs::write(path, dot).map_err(AocError::from)?;
+    }
+    let result = earliest_common_step(&ghosts?).context("process part 2")?;
     println!("{}", result);
     Ok(())
 }
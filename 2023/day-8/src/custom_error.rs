@@ -6,4 +6,36 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("failed to parse network: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("node `{0}` is not in the network")]
+    #[diagnostic(code(aoc::unknown_node))]
+    UnknownNode(String),
+
+    #[error("invalid node selector `{0}`; expected `exact:NAME`, `suffix:TEXT` or `glob:PATTERN`")]
+    #[diagnostic(code(aoc::invalid_selector))]
+    InvalidSelector(String),
+
+    #[error("{0} start nodes match, expected exactly one")]
+    #[diagnostic(code(aoc::ambiguous_start))]
+    AmbiguousStart(usize),
+
+    #[error("no end node is reachable from `{0}`")]
+    #[diagnostic(code(aoc::unreachable))]
+    Unreachable(String),
+
+    #[error("no start nodes")]
+    #[diagnostic(code(aoc::no_start))]
+    NoStart,
+
+    #[error("the ghosts are never all on end nodes at the same time")]
+    #[diagnostic(code(aoc::unsynchronized))]
+    Unsynchronized,
+
+    #[error("step count overflows")]
+    #[diagnostic(code(aoc::overflow))]
+    Overflow,
 }
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::network::{Direction, Network, NodeId, NodeSet};
+
+const CYCLE_COLORS: [&str; 6] = ["blue", "darkorange", "purple", "darkgreen", "red", "brown"];
+
+/// Renders the network as a Graphviz digraph. Start nodes are filled green
+/// and end nodes red. Each entry of `cycles` (see `GhostCycle::cycle_edges`)
+/// outlines its nodes and edges in its own colour.
+pub fn to_dot(
+    network: &Network,
+    starts: &NodeSet,
+    ends: &NodeSet,
+    cycles: &[Vec<(NodeId, Direction)>],
+) -> String {
+    let mut node_colors: Vec<Option<&str>> = vec![None; network.len()];
+    let mut edge_colors: HashMap<(NodeId, Direction), &str> = HashMap::new();
+    for (cycle, color) in cycles.iter().zip(CYCLE_COLORS.iter().cycle()) {
+        for edge in cycle {
+            node_colors[edge.0 as usize] = Some(color);
+            edge_colors.insert(*edge, color);
+        }
+    }
+
+    let mut out = String::from("digraph network {\n    node [shape=ellipse];\n");
+    for id in 0..network.len() as NodeId {
+        let mut attributes = Vec::new();
+        match (starts.contains(id), ends.contains(id)) {
+            (true, true) => attributes.push("style=filled, fillcolor=gold".to_string()),
+            (true, false) => attributes.push("style=filled, fillcolor=palegreen".to_string()),
+            (false, true) => attributes.push("style=filled, fillcolor=lightcoral".to_string()),
+            (false, false) => {}
+        }
+        if let Some(color) = node_colors[id as usize] {
+            attributes.push(format!("color={color}, penwidth=2"));
+        }
+        if !attributes.is_empty() {
+            writeln!(
+                out,
+                "    \"{}\" [{}];",
+                network.name(id),
+                attributes.join(", ")
+            )
+            .unwrap();
+        }
+    }
+    for id in 0..network.len() as NodeId {
+        let left = network.step(id, Direction::L);
+        let right = network.step(id, Direction::R);
+        let edges = if left == right {
+            vec![(left, "LR", [Direction::L, Direction::R].as_slice())]
+        } else {
+            vec![
+                (left, "L", [Direction::L].as_slice()),
+                (right, "R", [Direction::R].as_slice()),
+            ]
+        };
+        for (to, label, directions) in edges {
+            let color = directions
+                .iter()
+                .find_map(|direction| edge_colors.get(&(id, *direction)));
+            write!(
+                out,
+                "    \"{}\" -> \"{}\" [label=\"{label}\"",
+                network.name(id),
+                network.name(to)
+            )
+            .unwrap();
+            if let Some(color) = color {
+                write!(out, ", color={color}, penwidth=2").unwrap();
+            }
+            out.push_str("];\n");
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ghost::GhostCycle;
+
+    #[test]
+    fn test_to_dot() -> miette::Result<()> {
+        let network = Network::parse(
+            "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+XXX = (XXX, XXX)",
+        )?;
+        let starts = network.select(|name| name.ends_with('A'));
+        let ends = network.select(|name| name.ends_with('Z'));
+        let start = network.id("11A")?;
+        let cycle = GhostCycle::analyze(&network, start, &ends).cycle_edges(&network, start);
+        assert_eq!(
+            "digraph network {
+    node [shape=ellipse];
+    \"11A\" [style=filled, fillcolor=palegreen];
+    \"11B\" [color=blue, penwidth=2];
+    \"11Z\" [style=filled, fillcolor=lightcoral, color=blue, penwidth=2];
+    \"11A\" -> \"11B\" [label=\"L\"];
+    \"11A\" -> \"XXX\" [label=\"R\"];
+    \"11B\" -> \"XXX\" [label=\"L\"];
+    \"11B\" -> \"11Z\" [label=\"R\", color=blue, penwidth=2];
+    \"11Z\" -> \"11B\" [label=\"L\", color=blue, penwidth=2];
+    \"11Z\" -> \"XXX\" [label=\"R\"];
+    \"XXX\" -> \"XXX\" [label=\"LR\"];
+}
+",
+            to_dot(&network, &starts, &ends, &[cycle])
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+
+use crate::custom_error::AocError;
+use crate::network::{Direction, Network, NodeId, NodeSet};
+
+/// Where a single ghost ends up. The walk over states (node, instruction
+/// index) is eventually periodic: after `prefix` steps it repeats every
+/// `cycle` steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostCycle {
+    pub prefix: u64,
+    pub cycle: u64,
+    /// Steps before `prefix` at which the ghost is on an end node. These
+    /// happen only once.
+    pub prefix_hits: Vec<u64>,
+    /// Steps in `prefix..prefix + cycle` at which the ghost is on an end
+    /// node. These repeat every `cycle` steps.
+    pub cycle_hits: Vec<u64>,
+}
+
+impl GhostCycle {
+    /// Walks from `start` until a state repeats, recording every step at
+    /// which the ghost is on one of `ends`. Step 0 (the start itself) never
+    /// counts.
+    pub fn analyze(network: &Network, start: NodeId, ends: &NodeSet) -> GhostCycle {
+        let len = network.directions.len();
+        // First step at which each visited (node, instruction index) state
+        // was seen, so memory grows with the walk rather than the network
+        let mut seen: HashMap<(NodeId, u32), u64> = HashMap::new();
+        let mut hits = Vec::new();
+        let mut node = start;
+        let mut step = 0u64;
+        loop {
+            let idx = (step % len as u64) as usize;
+            if let Some(&first) = seen.get(&(node, idx as u32)) {
+                return GhostCycle {
+                    prefix: first,
+                    cycle: step - first,
+                    prefix_hits: hits.iter().copied().filter(|hit| *hit < first).collect(),
+                    cycle_hits: hits.iter().copied().filter(|hit| *hit >= first).collect(),
+                };
+            }
+            seen.insert((node, idx as u32), step);
+            if step > 0 && ends.contains(node) {
+                hits.push(step);
+            }
+            node = network.step(node, network.directions[idx]);
+            step += 1;
+        }
+    }
+
+    /// The edges the ghost starting at `start` keeps taking once it is in its
+    /// cycle, as (node, direction) pairs in walking order.
+    pub fn cycle_edges(&self, network: &Network, start: NodeId) -> Vec<(NodeId, Direction)> {
+        let len = network.directions.len() as u64;
+        let direction = |step: u64| network.directions[(step % len) as usize];
+        let node = (0..self.prefix).fold(start, |node, step| network.step(node, direction(step)));
+        (self.prefix..self.prefix + self.cycle)
+            .scan(node, |node, step| {
+                let edge = (*node, direction(step));
+                *node = network.step(*node, edge.1);
+                Some(edge)
+            })
+            .collect()
+    }
+
+    fn is_hit(&self, step: u64) -> bool {
+        if step < self.prefix {
+            self.prefix_hits.contains(&step)
+        } else {
+            let normalized = self.prefix + (step - self.prefix) % self.cycle;
+            self.cycle_hits.contains(&normalized)
+        }
+    }
+}
+
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// Solves `x = a1 (mod n1)` and `x = a2 (mod n2)` for moduli that need not be
+/// coprime. Returns `(x, lcm(n1, n2))`, or `None` if the congruences conflict.
+fn crt((a1, n1): (i128, i128), (a2, n2): (i128, i128)) -> Result<Option<(i128, i128)>, AocError> {
+    let (g, p, _) = extended_gcd(n1, n2);
+    if (a2 - a1) % g != 0 {
+        return Ok(None);
+    }
+    let lcm = (n1 / g).checked_mul(n2).ok_or(AocError::Overflow)?;
+    let k = ((a2 - a1) / g % (n2 / g))
+        .checked_mul(p % (n2 / g))
+        .ok_or(AocError::Overflow)?
+        .rem_euclid(n2 / g);
+    let x = n1
+        .checked_mul(k)
+        .and_then(|x| x.checked_add(a1))
+        .ok_or(AocError::Overflow)?
+        .rem_euclid(lcm);
+    Ok(Some((x, lcm)))
+}
+
+/// Analyses a ghost for every node in `starts`. Fails with `Unreachable` if
+/// some ghost never reaches an end node.
+pub fn ghosts(
+    network: &Network,
+    starts: &NodeSet,
+    ends: &NodeSet,
+) -> Result<Vec<GhostCycle>, AocError> {
+    starts
+        .iter()
+        .map(|start| {
+            let ghost = GhostCycle::analyze(network, start, ends);
+            if ghost.prefix_hits.is_empty() && ghost.cycle_hits.is_empty() {
+                Err(AocError::Unreachable(network.name(start).to_string()))
+            } else {
+                Ok(ghost)
+            }
+        })
+        .collect()
+}
+
+/// The first step (at least 1) at which every ghost is on an end node.
+pub fn earliest_common_step(ghosts: &[GhostCycle]) -> Result<u64, AocError> {
+    let Some(slowest) = ghosts.iter().max_by_key(|ghost| ghost.prefix) else {
+        return Err(AocError::NoStart);
+    };
+
+    // Before the slowest ghost enters its cycle, only its one-off hits count
+    if let Some(step) = slowest
+        .prefix_hits
+        .iter()
+        .find(|step| ghosts.iter().all(|ghost| ghost.is_hit(**step)))
+    {
+        return Ok(*step);
+    }
+
+    // From then on every ghost is cycling; combine their hits with CRT
+    let mut solutions: Vec<(i128, i128)> = vec![(0, 1)];
+    for ghost in ghosts {
+        let mut combined = Vec::new();
+        for solution in solutions.iter() {
+            for hit in ghost.cycle_hits.iter() {
+                let congruence = (*hit as i128 % ghost.cycle as i128, ghost.cycle as i128);
+                if let Some(solution) = crt(*solution, congruence)? {
+                    combined.push(solution);
+                }
+            }
+        }
+        combined.sort_unstable();
+        combined.dedup();
+        solutions = combined;
+    }
+
+    let from = slowest.prefix.max(1) as i128;
+    solutions
+        .into_iter()
+        .map(|(residue, modulus)| {
+            if residue >= from {
+                residue
+            } else {
+                residue + (from - residue + modulus - 1) / modulus * modulus
+            }
+        })
+        .min()
+        .ok_or(AocError::Unsynchronized)?
+        .try_into()
+        .map_err(|_| AocError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn parse_ghosts(input: &str) -> Result<Vec<GhostCycle>, AocError> {
+        let network = Network::parse(input)?;
+        let starts = network.select(|node| node.ends_with('A'));
+        let ends = network.select(|node| node.ends_with('Z'));
+        ghosts(&network, &starts, &ends)
+    }
+
+    #[test]
+    fn test_analyze() -> miette::Result<()> {
+        let ghosts = parse_ghosts(
+            "L
+
+1A = (1B, 1B)
+1B = (1Z, 1Z)
+1Z = (1C, 1C)
+1C = (1B, 1B)",
+        )?;
+        assert_eq!(
+            vec![GhostCycle {
+                prefix: 1,
+                cycle: 3,
+                prefix_hits: vec![],
+                cycle_hits: vec![2],
+            }],
+            ghosts
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    // First hits are 2 and 1, so a plain lcm would answer 2
+    #[case(
+        "L
+
+1A = (1B, 1B)
+1B = (1Z, 1Z)
+1Z = (1C, 1C)
+1C = (1B, 1B)
+2A = (2Z, 2Z)
+2Z = (2A, 2A)",
+        5
+    )]
+    // Hit only on the way into the cycle
+    #[case(
+        "L
+
+1A = (1Z, 1Z)
+1Z = (1B, 1B)
+1B = (1B, 1B)
+2A = (2Z, 2Z)
+2Z = (2Z, 2Z)",
+        1
+    )]
+    // Several end nodes within one cycle
+    #[case(
+        "L
+
+1A = (1Z, 1Z)
+1Z = (1YZ, 1YZ)
+1YZ = (1B, 1B)
+1B = (1A, 1A)
+2A = (2B, 2B)
+2B = (2C, 2C)
+2C = (2Z, 2Z)
+2Z = (2B, 2B)",
+        6
+    )]
+    fn test_earliest_common_step(#[case] input: &str, #[case] expected: u64) -> miette::Result<()> {
+        assert_eq!(expected, earliest_common_step(&parse_ghosts(input)?)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_never_synchronized() -> miette::Result<()> {
+        let ghosts = parse_ghosts(
+            "L
+
+1A = (1Z, 1Z)
+1Z = (1A, 1A)
+2A = (2B, 2B)
+2B = (2Z, 2Z)
+2Z = (2B, 2B)",
+        )?;
+        assert!(matches!(
+            earliest_common_step(&ghosts),
+            Err(AocError::Unsynchronized)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_unreachable_end() {
+        let result = parse_ghosts(
+            "L
+
+1A = (1Z, 1Z)
+1Z = (1A, 1A)
+2A = (2B, 2B)
+2B = (2A, 2A)",
+        );
+        assert!(matches!(result, Err(AocError::Unreachable(start)) if start == "2A"));
+    }
+}
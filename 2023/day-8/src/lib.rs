@@ -1,4 +1,8 @@
 pub mod custom_error;
+pub mod dot;
+pub mod ghost;
+pub mod network;
+pub mod selector;
 
 pub mod part1;
 pub mod part2;
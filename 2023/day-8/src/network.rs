@@ -0,0 +1,216 @@
+use std::collections::{HashMap, HashSet};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, char, line_ending, multispace1},
+    combinator::{all_consuming, opt},
+    multi::many1,
+    sequence::{delimited, separated_pair, terminated},
+    IResult, Parser,
+};
+
+use crate::custom_error::AocError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    L,
+    R,
+}
+
+/// Dense index of a node, assigned in input order.
+pub type NodeId = u32;
+
+type NodeLine<'a> = (&'a str, (&'a str, &'a str));
+
+/// The instructions and the node map from the puzzle input. Node names are
+/// interned at parse time so that walking is plain array indexing.
+#[derive(Debug, Clone)]
+pub struct Network<'a> {
+    pub directions: Vec<Direction>,
+    names: Vec<&'a str>,
+    ids: HashMap<&'a str, NodeId>,
+    left: Vec<NodeId>,
+    right: Vec<NodeId>,
+}
+
+/// A set of nodes stored as a bitset over node ids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeSet {
+    bits: Vec<u64>,
+}
+
+impl NodeSet {
+    pub fn new(len: usize) -> NodeSet {
+        NodeSet {
+            bits: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    pub fn insert(&mut self, id: NodeId) {
+        self.bits[id as usize / 64] |= 1 << (id % 64);
+    }
+
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.bits[id as usize / 64] & (1 << (id % 64)) != 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.bits.iter().enumerate().flat_map(|(idx, word)| {
+            (0..64)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| (idx * 64 + bit) as NodeId)
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|word| *word == 0)
+    }
+}
+
+fn parse_directions(input: &str) -> IResult<&str, Vec<Direction>> {
+    many1(alt((
+        char('L').map(|_| Direction::L),
+        char('R').map(|_| Direction::R),
+    )))(input)
+}
+
+fn parse_nodes(input: &str) -> IResult<&str, Vec<NodeLine<'_>>> {
+    many1(terminated(
+        separated_pair(
+            alphanumeric1,
+            tag(" = "),
+            delimited(
+                tag("("),
+                separated_pair(alphanumeric1, tag(", "), alphanumeric1),
+                tag(")"),
+            ),
+        ),
+        opt(line_ending),
+    ))(input)
+}
+
+impl<'a> Network<'a> {
+    pub fn parse(input: &'a str) -> Result<Network<'a>, AocError> {
+        let (_, (directions, lines)) =
+            all_consuming(separated_pair(parse_directions, multispace1, parse_nodes))(input)
+                .map_err(|e| AocError::ParseError(e.to_string()))?;
+
+        let names: Vec<&str> = lines.iter().map(|(name, _)| *name).collect();
+        let ids: HashMap<&str, NodeId> = names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (*name, id as NodeId))
+            .collect();
+        let lookup = |name: &str| {
+            ids.get(name)
+                .copied()
+                .ok_or_else(|| AocError::UnknownNode(name.to_string()))
+        };
+        let left = lines
+            .iter()
+            .map(|(_, (left, _))| lookup(left))
+            .collect::<Result<Vec<_>, _>>()?;
+        let right = lines
+            .iter()
+            .map(|(_, (_, right))| lookup(right))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Network {
+            directions,
+            names,
+            ids,
+            left,
+            right,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Result<NodeId, AocError> {
+        self.ids
+            .get(name)
+            .copied()
+            .ok_or_else(|| AocError::UnknownNode(name.to_string()))
+    }
+
+    pub fn name(&self, id: NodeId) -> &'a str {
+        self.names[id as usize]
+    }
+
+    pub fn step(&self, id: NodeId, direction: Direction) -> NodeId {
+        match direction {
+            Direction::L => self.left[id as usize],
+            Direction::R => self.right[id as usize],
+        }
+    }
+
+    /// Number of steps (at least 1) from `start` to the first node in `ends`.
+    /// Fails with `Unreachable` once a (node, instruction index) state
+    /// repeats without hitting an end, since the walk then loops forever.
+    pub fn steps_to(&self, start: NodeId, ends: &NodeSet) -> Result<u64, AocError> {
+        // Grows with the walk rather than the network, as in `GhostCycle`
+        let mut visited: HashSet<(NodeId, u32)> = HashSet::new();
+        let mut current = start;
+        let mut num_steps = 0;
+        for (idx, direction) in self.directions.iter().enumerate().cycle() {
+            if !visited.insert((current, idx as u32)) {
+                return Err(AocError::Unreachable(self.name(start).to_string()));
+            }
+            num_steps += 1;
+            current = self.step(current, *direction);
+            if ends.contains(current) {
+                break;
+            }
+        }
+        Ok(num_steps)
+    }
+
+    /// All nodes whose name satisfies `predicate`.
+    pub fn select(&self, predicate: impl Fn(&str) -> bool) -> NodeSet {
+        let mut set = NodeSet::new(self.len());
+        for (id, name) in self.names.iter().enumerate() {
+            if predicate(name) {
+                set.insert(id as NodeId);
+            }
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        let network = Network::parse(
+            "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+XXX = (XXX, XXX)",
+        )?;
+        assert_eq!(4, network.len());
+        let start = network.id("11A")?;
+        let next = network.step(start, Direction::L);
+        assert_eq!("11B", network.name(next));
+        assert_eq!("11Z", network.name(network.step(next, Direction::R)));
+        let ends = network.select(|name| name.ends_with('Z'));
+        assert_eq!(vec![2], ends.iter().collect::<Vec<_>>());
+        assert!(Network::parse("L\n\nAAA = (BBB, AAA)").is_err());
+        assert!(Network::parse("L\n\nAAA = (AAA, AAA)\n").is_ok());
+        assert!(matches!(
+            Network::parse("L\n\nAAA = (AAA, AAA)\ngarbage"),
+            Err(AocError::ParseError(_))
+        ));
+        Ok(())
+    }
+}
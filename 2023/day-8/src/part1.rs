@@ -1,67 +1,29 @@
-use std::collections::HashMap;
-
-use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{alpha1, char, line_ending, multispace1, newline},
-    multi::{fold_many1, many1},
-    sequence::{delimited, separated_pair, terminated},
-    IResult, Parser,
-};
-
 use crate::custom_error::AocError;
+use crate::network::Network;
+use crate::selector::Selector;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    L,
-    R,
-}
-
-fn parse_input(input: &str) -> IResult<&str, (Vec<Direction>, HashMap<&str, (&str, &str)>)> {
-    let (input, directions) = many1(alt((
-        char('L').map(|_| Direction::L),
-        char('R').map(|_| Direction::R),
-    )))(input)?;
-    let (input, _) = multispace1(input)?;
-    let (input, map) = fold_many1(
-        terminated(
-            separated_pair(
-                alpha1,
-                tag(" = "),
-                delimited(
-                    tag("("),
-                    separated_pair(alpha1, tag(", "), alpha1),
-                    tag(")"),
-                ),
-            ),
-            line_ending,
-        ),
-        HashMap::new,
-        |mut acc: HashMap<&str, (&str, &str)>, (key, value)| {
-            acc.insert(key, value);
-            acc
-        },
-    )(input)?;
-    Ok((input, (directions, map)))
+#[tracing::instrument]
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    let start = Selector::Exact("AAA".to_string());
+    let end = Selector::Exact("ZZZ".to_string());
+    process_with(input, &start, &end)
 }
 
+/// Steps from the single node matching `start` to any node matching `end`.
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let (_, (directions, map)) = parse_input(input).unwrap();
-    let mut current = "AAA";
-    let mut num_steps = 0;
-    for direction in directions.iter().cycle() {
-        num_steps += 1;
-        let (left, right) = map.get(current).unwrap();
-        match direction {
-            Direction::L => current = left,
-            Direction::R => current = right,
-        }
-        if current == "ZZZ" {
-            break;
-        }
-    }
-    Ok(num_steps)
+pub fn process_with(
+    input: &str,
+    start: &Selector,
+    end: &Selector,
+) -> miette::Result<u64, AocError> {
+    let network = Network::parse(input)?;
+    let starts = start.select(&network).iter().collect::<Vec<_>>();
+    let start = match starts.as_slice() {
+        [] => return Err(AocError::NoStart),
+        [start] => *start,
+        _ => return Err(AocError::AmbiguousStart(starts.len())),
+    };
+    network.steps_to(start, &end.select(&network))
 }
 
 #[cfg(test)]
@@ -93,4 +55,35 @@ ZZZ = (ZZZ, ZZZ)";
         assert_eq!(6, process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_unreachable() {
+        let input = "L
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+        assert!(matches!(process(input), Err(AocError::Unreachable(_))));
+    }
+
+    #[test]
+    fn test_selectors() -> miette::Result<()> {
+        let input = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+        let start = "glob:B*".parse()?;
+        let end = "suffix:Z".parse()?;
+        assert_eq!(3, process_with(input, &start, &end)?);
+        let start = "suffix:B".parse()?;
+        let end = "AAA".parse()?;
+        assert_eq!(1, process_with(input, &start, &end)?);
+        let start = "*".parse()?;
+        assert!(matches!(
+            process_with(input, &start, &end),
+            Err(AocError::AmbiguousStart(3))
+        ));
+        Ok(())
+    }
 }
@@ -0,0 +1,119 @@
+use std::str::FromStr;
+
+use crate::custom_error::AocError;
+use crate::network::{Network, NodeSet};
+
+/// Picks start or end nodes by name.
+///
+/// Parsed from `exact:AAA`, `suffix:Z` or `glob:?1*`. Without a prefix, a
+/// pattern containing `*` or `?` is a glob and anything else an exact name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selector {
+    Exact(String),
+    Suffix(String),
+    /// `*` matches any run of characters, `?` exactly one.
+    Glob(String),
+}
+
+impl Selector {
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Selector::Exact(exact) => name == exact,
+            Selector::Suffix(suffix) => name.ends_with(suffix.as_str()),
+            Selector::Glob(pattern) => glob_matches(pattern.as_bytes(), name.as_bytes()),
+        }
+    }
+
+    pub fn select(&self, network: &Network) -> NodeSet {
+        network.select(|name| self.matches(name))
+    }
+
+    fn pattern(&self) -> &str {
+        match self {
+            Selector::Exact(pattern) | Selector::Suffix(pattern) | Selector::Glob(pattern) => {
+                pattern
+            }
+        }
+    }
+}
+
+/// Iterative wildcard matching that backtracks only to the last `*`.
+fn glob_matches(pattern: &[u8], name: &[u8]) -> bool {
+    let (mut p, mut n) = (0, 0);
+    let mut star = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == b'?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == b'*')
+}
+
+impl FromStr for Selector {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Selector, AocError> {
+        let selector = match s.split_once(':') {
+            Some(("exact", name)) => Selector::Exact(name.to_string()),
+            Some(("suffix", suffix)) => Selector::Suffix(suffix.to_string()),
+            Some(("glob", pattern)) => Selector::Glob(pattern.to_string()),
+            Some(_) => return Err(AocError::InvalidSelector(s.to_string())),
+            None if s.contains(['*', '?']) => Selector::Glob(s.to_string()),
+            None => Selector::Exact(s.to_string()),
+        };
+        if selector.pattern().is_empty() {
+            return Err(AocError::InvalidSelector(s.to_string()));
+        }
+        Ok(selector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("AAA", "AAA", true)]
+    #[case("AAA", "AAB", false)]
+    #[case("exact:A*", "A*", true)]
+    #[case("suffix:Z", "11Z", true)]
+    #[case("suffix:Z", "Z1A", false)]
+    #[case("1?Z", "11Z", true)]
+    #[case("1?Z", "11ZZ", false)]
+    #[case("*Z", "22Z", true)]
+    #[case("glob:*A*", "BAB", true)]
+    #[case("glob:1*2*3", "1x3", false)]
+    #[case("glob:1*2*3", "1x2yy3", true)]
+    fn test_matches(#[case] selector: &str, #[case] name: &str, #[case] expected: bool) {
+        let selector: Selector = selector.parse().unwrap();
+        assert_eq!(expected, selector.matches(name));
+    }
+
+    #[rstest]
+    #[case("regex:A+")]
+    #[case("suffix:")]
+    #[case("")]
+    fn test_invalid(#[case] selector: &str) {
+        assert!(matches!(
+            selector.parse::<Selector>(),
+            Err(AocError::InvalidSelector(_))
+        ));
+    }
+}
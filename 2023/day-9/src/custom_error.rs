@@ -6,4 +6,16 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error("failed to parse sequences: {0}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError(String),
+
+    #[error("cannot extrapolate an empty sequence")]
+    #[diagnostic(code(aoc::empty_sequence))]
+    EmptySequence,
+
+    #[error("extrapolated value overflows i128")]
+    #[diagnostic(code(aoc::overflow))]
+    Overflow,
 }
@@ -1,5 +1,5 @@
-#![feature(iter_map_windows)]
 pub mod custom_error;
+pub mod polynomial;
 
 pub mod part1;
 pub mod part2;
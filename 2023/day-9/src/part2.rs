@@ -1,32 +1,14 @@
-use nom::{
-    character::complete::{i64, line_ending, space1},
-    multi::separated_list1,
-    IResult,
-};
-
 use crate::custom_error::AocError;
-
-fn parse_input(input: &str) -> IResult<&str, Vec<Vec<i64>>> {
-    fn parse_line(input: &str) -> IResult<&str, Vec<i64>> {
-        separated_list1(space1, i64)(input)
-    }
-
-    separated_list1(line_ending, parse_line)(input)
-}
-
-fn predict_next_value(mut input: Vec<i64>) -> i64 {
-    let mut first_values = Vec::<i64>::new();
-    while !input.iter().all(|v| v == &0) {
-        first_values.insert(0, input.first().unwrap().clone());
-        input = input.iter().map_windows(|&[a, b]| b - a).collect();
-    }
-    first_values.iter().fold(0, |acc, val| val - acc)
-}
+use crate::polynomial::{parse_sequences, Polynomial};
 
 #[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<i64, AocError> {
-    let (_, values) = parse_input(input).unwrap();
-    Ok(values.into_iter().map(|v| predict_next_value(v)).sum())
+pub fn process(input: &str) -> miette::Result<i128, AocError> {
+    parse_sequences(input)?
+        .iter()
+        .map(|values| Polynomial::fit(values)?.backward(1))
+        .try_fold(0i128, |total, value| {
+            total.checked_add(value?).ok_or(AocError::Overflow)
+        })
 }
 
 #[cfg(test)]
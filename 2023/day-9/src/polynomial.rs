@@ -0,0 +1,160 @@
+use nom::{
+    character::complete::{i64, line_ending, space1},
+    combinator::{all_consuming, opt},
+    multi::separated_list1,
+    sequence::terminated,
+    IResult,
+};
+
+use crate::custom_error::AocError;
+
+fn parse_line(input: &str) -> IResult<&str, Vec<i64>> {
+    separated_list1(space1, i64)(input)
+}
+
+pub fn parse_sequences(input: &str) -> Result<Vec<Vec<i64>>, AocError> {
+    all_consuming(terminated(
+        separated_list1(line_ending, parse_line),
+        opt(line_ending),
+    ))(input)
+    .map(|(_, sequences)| sequences)
+    .map_err(|e| AocError::ParseError(e.to_string()))
+}
+
+/// The lowest-degree polynomial through a sequence `f(0), f(1), ..,
+/// f(n - 1)`, kept in Newton form:
+/// `f(x) = sum over k of C(x, k) * diff_k`, where `diff_k` is the first
+/// entry of the k-th row of differences.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    differences: Vec<i128>,
+    len: usize,
+}
+
+impl Polynomial {
+    pub fn fit(values: &[i64]) -> Result<Polynomial, AocError> {
+        if values.is_empty() {
+            return Err(AocError::EmptySequence);
+        }
+        let mut row: Vec<i128> = values.iter().map(|value| *value as i128).collect();
+        let mut differences = Vec::with_capacity(row.len());
+        while !row.iter().all(|value| *value == 0) {
+            differences.push(row[0]);
+            row = row
+                .windows(2)
+                .map(|pair| pair[1].checked_sub(pair[0]).ok_or(AocError::Overflow))
+                .collect::<Result<_, _>>()?;
+        }
+        Ok(Polynomial {
+            differences,
+            len: values.len(),
+        })
+    }
+
+    /// Degree of the polynomial, taking the zero polynomial as degree 0. A
+    /// sequence whose differences never settle has degree `len - 1`.
+    pub fn degree(&self) -> usize {
+        self.differences.len().saturating_sub(1)
+    }
+
+    /// The value at index `x` of the original sequence, which may lie before
+    /// or after it.
+    pub fn value_at(&self, x: i64) -> Result<i128, AocError> {
+        let x = x as i128;
+        let mut total: i128 = 0;
+        // C(x, 0) = 1 and C(x, k) = C(x, k - 1) * (x - k + 1) / k, which
+        // divides exactly for every integer x
+        let mut binomial: i128 = 1;
+        for (k, difference) in self.differences.iter().enumerate() {
+            if k > 0 {
+                let k = k as i128;
+                binomial = binomial.checked_mul(x - k + 1).ok_or(AocError::Overflow)? / k;
+            }
+            let term = binomial
+                .checked_mul(*difference)
+                .ok_or(AocError::Overflow)?;
+            total = total.checked_add(term).ok_or(AocError::Overflow)?;
+        }
+        Ok(total)
+    }
+
+    /// The value `steps` places after the last one in the sequence.
+    pub fn forward(&self, steps: i64) -> Result<i128, AocError> {
+        let x = (self.len as i64 - 1)
+            .checked_add(steps)
+            .ok_or(AocError::Overflow)?;
+        self.value_at(x)
+    }
+
+    /// The value `steps` places before the first one in the sequence.
+    pub fn backward(&self, steps: i64) -> Result<i128, AocError> {
+        self.value_at(steps.checked_neg().ok_or(AocError::Overflow)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(&[0, 3, 6, 9, 12, 15], 1, 18, -3)]
+    #[case(&[1, 3, 6, 10, 15, 21], 2, 28, 0)]
+    #[case(&[10, 13, 16, 21, 30, 45], 3, 68, 5)]
+    #[case(&[0, 0, 0], 0, 0, 0)]
+    #[case(&[7], 0, 7, 7)]
+    fn test_fit(
+        #[case] values: &[i64],
+        #[case] degree: usize,
+        #[case] next: i128,
+        #[case] previous: i128,
+    ) -> miette::Result<()> {
+        let polynomial = Polynomial::fit(values)?;
+        assert_eq!(degree, polynomial.degree());
+        assert_eq!(next, polynomial.forward(1)?);
+        assert_eq!(previous, polynomial.backward(1)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_far_offsets() -> miette::Result<()> {
+        // n^3 - 2n
+        let values: Vec<i64> = (0..6).map(|n: i64| n.pow(3) - 2 * n).collect();
+        let polynomial = Polynomial::fit(&values)?;
+        for x in [-1000, -7, 0, 5, 42, 1_000_000] {
+            let x = x as i128;
+            assert_eq!(x.pow(3) - 2 * x, polynomial.value_at(x as i64)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflow() -> miette::Result<()> {
+        let polynomial = Polynomial::fit(&[0, 0, 0, 0, 1])?;
+        assert!(matches!(
+            polynomial.value_at(i64::MAX),
+            Err(AocError::Overflow)
+        ));
+        let constant = Polynomial::fit(&[7, 7])?;
+        assert!(matches!(
+            constant.forward(i64::MAX),
+            Err(AocError::Overflow)
+        ));
+        assert!(matches!(
+            constant.backward(i64::MIN),
+            Err(AocError::Overflow)
+        ));
+        assert_eq!(7, constant.backward(i64::MAX)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            parse_sequences("1 2 x"),
+            Err(AocError::ParseError(_))
+        ));
+        assert!(matches!(Polynomial::fit(&[]), Err(AocError::EmptySequence)));
+    }
+}
@@ -1,15 +1,3 @@
-#!/usr/bin/env -S cargo +nightly -Zscript
-
-//! ```cargo
-//! [package]
-//! edition = "2021"
-//!
-//! [dependencies]
-//! clap = { version = "4.2", features = ["derive"] }
-//! nom = "7.1.3"
-//! reqwest = { version = "0.11.22", features=["blocking"] }
-//! ```
-
 use clap::{error::ErrorKind, CommandFactory, Parser};
 use nom::{bytes::complete::tag, character::complete, sequence::preceded, IResult};
 use reqwest::{blocking::Client, header::COOKIE};